@@ -0,0 +1,174 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use strum::EnumString;
+
+use crate::{ConfigError, Puzzle};
+
+pub const CONFIG_FILENAME: &str = "aoc.toml";
+
+// Parameters each day accepts, from its `[days.<day>.parameters]` table or from `--param`, with
+// the lowest value they allow.
+const DAY_PARAMETERS: &[(&str, &[(&str, usize)])] = &[
+  ("day1", &[("window", 1), ("stride", 1), ("period", 1)]),
+  ("day6", &[("days", 0)]),
+  ("day11", &[("steps", 0)]),
+  ("day14", &[("cycles", 0)]),
+  ("day15", &[("repeat", 1)]),
+];
+
+pub fn validate_parameter(day: &str, name: &str, value: usize) -> Result<(), ConfigError> {
+  let min = DAY_PARAMETERS
+    .iter()
+    .find(|(parameter_day, _)| *parameter_day == day)
+    .and_then(|(_, parameters)| parameters.iter().find(|(parameter, _)| *parameter == name))
+    .map(|(_, min)| *min)
+    .ok_or_else(|| ConfigError::UnknownParameter {
+      day: day.to_string(),
+      name: name.to_string(),
+    })?;
+
+  if value < min {
+    return Err(ConfigError::InvalidParameter {
+      day: day.to_string(),
+      name: name.to_string(),
+      value,
+      min,
+    });
+  }
+
+  Ok(())
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum OutputFormat {
+  Plain,
+  Json,
+}
+
+impl Default for OutputFormat {
+  fn default() -> Self {
+    Self::Plain
+  }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RunnerConfig {
+  pub format: Option<OutputFormat>,
+  pub timeout: Option<u64>,
+  pub threads: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DayConfig {
+  pub input: Option<PathBuf>,
+  pub puzzle: Option<Puzzle>,
+  #[serde(default)]
+  pub parameters: BTreeMap<String, usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+  #[serde(default)]
+  pub runner: RunnerConfig,
+  #[serde(default)]
+  pub days: BTreeMap<String, DayConfig>,
+}
+
+impl Config {
+  pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+    let path = path.as_ref();
+    let mut config = fs::read_to_string(path)?.parse::<Self>()?;
+
+    // input paths are relative to the configuration file
+    if let Some(directory) = path.parent() {
+      for day in config.days.values_mut() {
+        day.input = day.input.take().map(|input| directory.join(input));
+      }
+    }
+
+    Ok(config)
+  }
+
+  // Looks for the configuration file in the current directory and its ancestors.
+  pub fn discover() -> Result<Option<Self>, ConfigError> {
+    let current_directory = std::env::current_dir()?;
+
+    current_directory
+      .ancestors()
+      .map(|directory| directory.join(CONFIG_FILENAME))
+      .find(|path| path.is_file())
+      .map(Self::from_file)
+      .transpose()
+  }
+
+  pub fn get_day(&self, day: &str) -> Option<&DayConfig> {
+    self.days.get(day)
+  }
+}
+
+impl std::str::FromStr for Config {
+  type Err = ConfigError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let config = toml::from_str::<Self>(s)?;
+
+    if let Some(day) = config.days.keys().find(|day| !is_valid_day(day)) {
+      return Err(ConfigError::UnknownDay(day.clone()));
+    }
+
+    for (day, day_config) in config.days.iter() {
+      for (name, value) in day_config.parameters.iter() {
+        validate_parameter(day, name, *value)?;
+      }
+    }
+
+    Ok(config)
+  }
+}
+
+fn is_valid_day(day: &str) -> bool {
+  day
+    .strip_prefix("day")
+    .and_then(|n| n.parse::<u8>().ok())
+    .map_or(false, |n| (1..=25).contains(&n) && !day.starts_with("day0"))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_rejects_unknown_keys() {
+    assert!(matches!(
+      "[runner]\nthread = 4".parse::<Config>(),
+      Err(ConfigError::Toml(_))
+    ));
+    assert!(matches!(
+      "[days.day26]\npuzzle = \"part1\"".parse::<Config>(),
+      Err(ConfigError::UnknownDay(_))
+    ));
+  }
+
+  #[test]
+  fn it_rejects_unknown_parameters_of_any_day() {
+    assert!("[days.day6.parameters]\ndays = 256"
+      .parse::<Config>()
+      .is_ok());
+    assert!(matches!(
+      "[days.day6.parameters]\ndays = 256\n[days.day7.parameters]\ndays = 256".parse::<Config>(),
+      Err(ConfigError::UnknownParameter { day, name }) if day == "day7" && name == "days"
+    ));
+    assert!(matches!(
+      "[days.day1.parameters]\nwindow = 0".parse::<Config>(),
+      Err(ConfigError::InvalidParameter { name, value: 0, min: 1, .. }) if name == "window"
+    ));
+  }
+}
@@ -0,0 +1,123 @@
+use std::io;
+use std::num::ParseIntError;
+use std::str::Utf8Error;
+use std::time::Duration;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum InputError {
+  #[error("unable to read input")]
+  Io(#[from] io::Error),
+  #[error("invalid input at line {line}")]
+  Line {
+    line: usize,
+    #[source]
+    source: ParseError,
+  },
+  #[error("invalid input at line {line}")]
+  LineValidation {
+    line: usize,
+    #[source]
+    source: ValidationError,
+  },
+  #[error(transparent)]
+  Parse(#[from] ParseError),
+  #[error(transparent)]
+  Validation(#[from] ValidationError),
+}
+
+#[derive(Debug, Error)]
+pub enum ParseError {
+  #[error("invalid '{value}' {kind}")]
+  InvalidChar { kind: &'static str, value: char },
+  // columns are 1-based, as for `MalformedAt`
+  #[error("invalid '{value}' {kind} at column {column}")]
+  InvalidCharAt {
+    kind: &'static str,
+    value: char,
+    column: usize,
+  },
+  #[error("malformed {kind}: '{value}'")]
+  Malformed { kind: &'static str, value: String },
+  #[error("malformed {kind} at column {column}: '{value}'")]
+  MalformedAt {
+    kind: &'static str,
+    value: String,
+    column: usize,
+  },
+  #[error("missing {separator:?} separator in {kind}")]
+  MissingSeparator {
+    kind: &'static str,
+    separator: &'static str,
+  },
+  #[error(transparent)]
+  Utf8(#[from] Utf8Error),
+  #[error(transparent)]
+  Integer(#[from] ParseIntError),
+  #[error(transparent)]
+  Format(#[from] parse_display::ParseError),
+  #[error(transparent)]
+  Variant(#[from] strum::ParseError),
+}
+
+#[derive(Debug, Error)]
+pub enum ValidationError {
+  #[error("empty {kind}")]
+  Empty { kind: &'static str },
+  #[error("missing {kind}")]
+  Missing { kind: &'static str },
+  #[error("invalid {kind} size (expected {expected} but got {actual})")]
+  Size {
+    kind: &'static str,
+    expected: usize,
+    actual: usize,
+  },
+  #[error("expected {width} by {height} {kind} grid")]
+  GridSize {
+    kind: &'static str,
+    width: usize,
+    height: usize,
+  },
+  #[error("invalid {kind}: row length should be all the same")]
+  UnevenRows { kind: &'static str },
+  #[error("unsupported {kind} '{value}'")]
+  Unsupported { kind: &'static str, value: String },
+  #[error("{kind} does not fit in {bits} bits")]
+  Overflow { kind: &'static str, bits: u32 },
+  #[error("{kind} too large ({actual} over the {max} limit)")]
+  TooLarge {
+    kind: &'static str,
+    max: u64,
+    actual: u64,
+  },
+}
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+  #[error("unable to read configuration")]
+  Io(#[from] io::Error),
+  #[error("invalid configuration")]
+  Toml(#[from] toml::de::Error),
+  #[error("unknown '{0}' day in configuration")]
+  UnknownDay(String),
+  #[error("unknown '{name}' parameter for {day}")]
+  UnknownParameter { day: String, name: String },
+  #[error("invalid '{name}' parameter for {day}: {value} is lower than {min}")]
+  InvalidParameter {
+    day: String,
+    name: String,
+    value: usize,
+    min: usize,
+  },
+  #[error("missing {0}: pass it on the command line or set it in the configuration")]
+  Missing(&'static str),
+}
+
+#[derive(Debug, Error)]
+pub enum RunnerError {
+  #[error("puzzle not solved within {0:?}")]
+  Timeout(Duration),
+  #[error("puzzle solving aborted")]
+  Aborted,
+}
@@ -0,0 +1,112 @@
+use std::fs::File;
+use std::io::Read;
+use std::ops::Deref;
+use std::path::Path;
+use std::str::{self, FromStr};
+
+use memmap2::Mmap;
+
+use crate::{InputError, ParseError};
+
+pub enum Input {
+  Mapped(Mmap),
+  Buffered(Vec<u8>),
+}
+
+impl Input {
+  pub fn open(path: impl AsRef<Path>) -> Result<Self, InputError> {
+    let file = File::open(path)?;
+    // the mapping is only sound as long as no other process truncates the file meanwhile,
+    // which is an acceptable trade-off for read-only puzzle inputs
+    let mmap = unsafe { Mmap::map(&file)? };
+
+    Ok(Self::Mapped(mmap))
+  }
+
+  pub fn from_reader(mut reader: impl Read) -> Result<Self, InputError> {
+    let mut buffer = Vec::new();
+
+    reader.read_to_end(&mut buffer)?;
+
+    Ok(Self::Buffered(buffer))
+  }
+
+  pub fn lines(&self) -> impl Iterator<Item = &[u8]> {
+    let bytes = match self.strip_suffix(b"\n") {
+      Some(bytes) => bytes,
+      None => self,
+    };
+
+    bytes
+      .split(|byte| *byte == b'\n')
+      .filter(move |_| !bytes.is_empty())
+      .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+  }
+
+  pub fn parse_lines<T>(&self) -> Result<Vec<T>, InputError>
+  where
+    T: FromStr,
+    T::Err: Into<ParseError>,
+  {
+    self
+      .lines()
+      .enumerate()
+      .map(|(i, line)| {
+        parse_bytes(line).map_err(|source| InputError::Line {
+          line: i + 1,
+          source,
+        })
+      })
+      .collect()
+  }
+}
+
+impl From<&str> for Input {
+  fn from(s: &str) -> Self {
+    Self::Buffered(s.as_bytes().to_vec())
+  }
+}
+
+impl From<String> for Input {
+  fn from(s: String) -> Self {
+    Self::Buffered(s.into_bytes())
+  }
+}
+
+impl Deref for Input {
+  type Target = [u8];
+
+  fn deref(&self) -> &[u8] {
+    match self {
+      Self::Mapped(mmap) => mmap,
+      Self::Buffered(buffer) => buffer,
+    }
+  }
+}
+
+impl AsRef<[u8]> for Input {
+  fn as_ref(&self) -> &[u8] {
+    self
+  }
+}
+
+pub fn parse_bytes<T>(bytes: &[u8]) -> Result<T, ParseError>
+where
+  T: FromStr,
+  T::Err: Into<ParseError>,
+{
+  str::from_utf8(bytes)?.parse::<T>().map_err(Into::into)
+}
+
+pub fn trim_bytes(bytes: &[u8]) -> &[u8] {
+  let start = bytes
+    .iter()
+    .position(|byte| !byte.is_ascii_whitespace())
+    .unwrap_or(bytes.len());
+  let end = bytes
+    .iter()
+    .rposition(|byte| !byte.is_ascii_whitespace())
+    .map_or(start, |i| i + 1);
+
+  &bytes[start..end]
+}
@@ -1,36 +1,52 @@
+pub mod config;
+mod error;
+mod input;
+mod runner;
+
 use std::io::{BufRead, BufReader, Read};
 use std::str::FromStr;
 
-use strum::EnumString;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
+
+pub use error::{ConfigError, InputError, ParseError, RunnerError, ValidationError};
+pub use input::{parse_bytes, trim_bytes, Input};
+pub use runner::{Runner, RunnerOpt};
 
 pub type Error = eyre::Error;
 pub type Result<T> = eyre::Result<T>;
 
-#[derive(EnumString)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display, EnumString, Serialize, Deserialize)]
 #[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum Puzzle {
   Part1,
   Part2,
 }
 
-pub fn read_lines<T>(reader: impl Read) -> Result<Vec<T>>
+pub fn read_lines<T>(reader: impl Read) -> std::result::Result<Vec<T>, InputError>
 where
   T: FromStr,
-  T::Err: Into<Error>,
+  T::Err: Into<ParseError>,
 {
   let reader = BufReader::new(reader);
 
   reader
     .lines()
-    .map(|line| line.map(|x| x.parse::<T>().map_err(Into::into)))
-    .flatten()
-    .collect::<Result<Vec<_>>>()
+    .enumerate()
+    .map(|(i, line)| {
+      line?.parse::<T>().map_err(|e| InputError::Line {
+        line: i + 1,
+        source: e.into(),
+      })
+    })
+    .collect()
 }
 
-pub fn split_line<T>(reader: impl Read, separator: &str) -> Result<Vec<T>>
+pub fn split_line<T>(reader: impl Read, separator: &str) -> std::result::Result<Vec<T>, InputError>
 where
   T: FromStr,
-  T::Err: Into<Error>,
+  T::Err: Into<ParseError>,
 {
   let mut reader = BufReader::new(reader);
   let mut buffer = String::new();
@@ -39,6 +55,38 @@ where
 
   buffer
     .split(separator)
-    .map(|x| x.trim().parse::<T>().map_err(Into::into))
-    .collect::<Result<Vec<_>>>()
+    .map(|x| {
+      x.trim()
+        .parse::<T>()
+        .map_err(|e| InputError::from(e.into()))
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn it_reports_invalid_line_number() {
+    let result = read_lines::<usize>("1\n2\nthree\n4".as_bytes());
+
+    assert!(matches!(
+      result,
+      Err(InputError::Line {
+        line: 3,
+        source: ParseError::Integer(_)
+      })
+    ));
+  }
+
+  #[test]
+  fn it_splits_input_lines() -> std::result::Result<(), InputError> {
+    let input = Input::from_reader("1\r\n\n3\n".as_bytes())?;
+
+    assert_eq!(input.lines().collect::<Vec<_>>(), [&b"1"[..], b"", b"3"]);
+    assert_eq!(Input::from_reader("".as_bytes())?.lines().count(), 0);
+
+    Ok(())
+  }
 }
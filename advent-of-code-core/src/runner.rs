@@ -0,0 +1,292 @@
+use std::fmt::Display;
+use std::io::{self, BufRead, BufReader, Cursor};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use serde::Serialize;
+use structopt::StructOpt;
+
+use crate::config::{self, Config, OutputFormat};
+use crate::{ConfigError, Input, ParseError, Puzzle, RunnerError};
+
+const STDIN_FILENAME: &str = "-";
+
+#[derive(StructOpt)]
+pub struct RunnerOpt {
+  #[structopt(short, long)]
+  filename: Option<PathBuf>,
+  #[structopt(short, long)]
+  puzzle: Option<Puzzle>,
+  /// Configuration file, defaults to the closest aoc.toml
+  #[structopt(short, long)]
+  config: Option<PathBuf>,
+  /// Output format (plain or json)
+  #[structopt(long)]
+  format: Option<OutputFormat>,
+  /// Maximum solving time in seconds
+  #[structopt(long)]
+  timeout: Option<u64>,
+  /// Number of threads for the days solving in parallel
+  #[structopt(long)]
+  threads: Option<usize>,
+  /// Day parameter override, e.g. `--param days=256`
+  #[structopt(long = "param", number_of_values = 1)]
+  parameters: Vec<ParameterOverride>,
+}
+
+struct ParameterOverride {
+  name: String,
+  value: usize,
+}
+
+impl FromStr for ParameterOverride {
+  type Err = ParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (name, value) = s.split_once('=').ok_or(ParseError::MissingSeparator {
+      kind: "parameter",
+      separator: "=",
+    })?;
+
+    Ok(Self {
+      name: name.trim().to_string(),
+      value: value.trim().parse()?,
+    })
+  }
+}
+
+#[derive(Serialize)]
+struct Report<'a, T> {
+  day: &'a str,
+  puzzle: Puzzle,
+  result: T,
+}
+
+pub struct Runner {
+  day: &'static str,
+  // only required by the days reading an input
+  filename: Option<PathBuf>,
+  puzzle: Puzzle,
+  format: OutputFormat,
+  timeout: Option<Duration>,
+  threads: usize,
+  parameters: Vec<(String, usize)>,
+}
+
+impl Runner {
+  pub fn new(day: &'static str, opt: RunnerOpt) -> Result<Self, ConfigError> {
+    let config = match &opt.config {
+      Some(path) => Config::from_file(path)?,
+      None => Config::discover()?.unwrap_or_default(),
+    };
+
+    let day_config = config.get_day(day);
+    let mut parameters = day_config
+      .map(|day_config| day_config.parameters.clone())
+      .unwrap_or_default();
+
+    // the configuration is validated on load, only the command line overrides are left to check
+    for parameter in opt.parameters {
+      config::validate_parameter(day, &parameter.name, parameter.value)?;
+      parameters.insert(parameter.name, parameter.value);
+    }
+
+    let filename = opt
+      .filename
+      .or_else(|| day_config.and_then(|day_config| day_config.input.clone()));
+    let puzzle = opt
+      .puzzle
+      .or_else(|| day_config.and_then(|day_config| day_config.puzzle))
+      .ok_or(ConfigError::Missing("puzzle"))?;
+    let threads = opt.threads.or(config.runner.threads).unwrap_or(1);
+
+    Ok(Self {
+      day,
+      filename,
+      puzzle,
+      format: opt.format.or(config.runner.format).unwrap_or_default(),
+      timeout: opt
+        .timeout
+        .or(config.runner.timeout)
+        .map(Duration::from_secs),
+      threads,
+      parameters: parameters.into_iter().collect(),
+    })
+  }
+
+  pub fn get_parameter(&self, name: &str) -> Option<usize> {
+    self
+      .parameters
+      .iter()
+      .find(|(parameter, _)| parameter == name)
+      .map(|(_, value)| *value)
+  }
+
+  pub fn get_threads(&self) -> usize {
+    self.threads
+  }
+
+  pub fn run<T, F>(self, solve: F) -> crate::Result<()>
+  where
+    T: Display + Serialize + Send + 'static,
+    F: FnOnce(Input, Puzzle) -> crate::Result<T> + Send + 'static,
+  {
+    let input = Input::open(self.get_filename()?)?;
+
+    self.execute(move |puzzle| solve(input, puzzle))
+  }
+
+  // Same as `run` but hands out a buffered reader instead of the whole input. Files are still
+  // mapped in memory, only the standard input, when the filename is `-`, is read as a stream.
+  pub fn run_reader<T, F>(self, solve: F) -> crate::Result<()>
+  where
+    T: Display + Serialize + Send + 'static,
+    F: FnOnce(Box<dyn BufRead + Send>, Puzzle) -> crate::Result<T> + Send + 'static,
+  {
+    let filename = self.get_filename()?;
+    let reader: Box<dyn BufRead + Send> = if filename.as_os_str() == STDIN_FILENAME {
+      Box::new(BufReader::new(io::stdin()))
+    } else {
+      Box::new(Cursor::new(Input::open(filename)?))
+    };
+
+    self.execute(move |puzzle| solve(reader, puzzle))
+  }
+
+  // Same as `run` for the modes which do not read any input, so that none has to be given.
+  pub fn run_without_input<T, F>(self, solve: F) -> crate::Result<()>
+  where
+    T: Display + Serialize + Send + 'static,
+    F: FnOnce(Puzzle) -> crate::Result<T> + Send + 'static,
+  {
+    self.execute(solve)
+  }
+
+  fn get_filename(&self) -> Result<&Path, ConfigError> {
+    self
+      .filename
+      .as_deref()
+      .ok_or(ConfigError::Missing("input filename"))
+  }
+
+  fn execute<T, F>(self, solve: F) -> crate::Result<()>
+  where
+    T: Display + Serialize + Send + 'static,
+    F: FnOnce(Puzzle) -> crate::Result<T> + Send + 'static,
+  {
+    let puzzle = self.puzzle;
+    let (sender, receiver) = mpsc::channel();
+
+    // solve in a dedicated thread so that a timeout can give up on it
+    thread::Builder::new()
+      .name(self.day.to_string())
+      .spawn(move || sender.send(solve(puzzle)))?;
+
+    let result = match self.timeout {
+      Some(timeout) => receiver.recv_timeout(timeout).map_err(|e| match e {
+        mpsc::RecvTimeoutError::Timeout => RunnerError::Timeout(timeout),
+        mpsc::RecvTimeoutError::Disconnected => RunnerError::Aborted,
+      })?,
+      None => receiver.recv().map_err(|_| RunnerError::Aborted)?,
+    }?;
+
+    match self.format {
+      OutputFormat::Plain => println!("{}", result),
+      OutputFormat::Json => println!(
+        "{}",
+        serde_json::to_string(&Report {
+          day: self.day,
+          puzzle,
+          result,
+        })?
+      ),
+    }
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{env, fs, process};
+
+  use super::*;
+
+  #[test]
+  fn it_lets_command_line_flags_take_precedence() -> crate::Result<()> {
+    let path = env::temp_dir().join(format!("aoc-runner-{}.toml", process::id()));
+
+    fs::write(
+      &path,
+      "[runner]\nformat = \"json\"\ntimeout = 60\nthreads = 2\n\n\
+       [days.day6]\ninput = \"day6.txt\"\npuzzle = \"part1\"\n\n\
+       [days.day6.parameters]\ndays = 80\n",
+    )?;
+
+    let create_runner = |args: &[&str]| {
+      let config = path.to_string_lossy().into_owned();
+      let opt = RunnerOpt::from_iter_safe(["day6", "--config", &config].iter().chain(args.iter()))
+        .expect("invalid runner options");
+
+      Runner::new("day6", opt)
+    };
+
+    let runner = create_runner(&[])?;
+
+    assert_eq!(runner.filename, Some(path.with_file_name("day6.txt")));
+    assert_eq!(runner.puzzle, Puzzle::Part1);
+    assert_eq!(runner.format, OutputFormat::Json);
+    assert_eq!(runner.timeout, Some(Duration::from_secs(60)));
+    assert_eq!(runner.get_threads(), 2);
+    assert_eq!(runner.get_parameter("days"), Some(80));
+
+    let runner = create_runner(&[
+      "-f",
+      "other.txt",
+      "-p",
+      "part2",
+      "--format",
+      "plain",
+      "--timeout",
+      "5",
+      "--threads",
+      "4",
+      "--param",
+      "days=256",
+    ])?;
+
+    assert_eq!(runner.filename, Some(PathBuf::from("other.txt")));
+    assert_eq!(runner.puzzle, Puzzle::Part2);
+    assert_eq!(runner.format, OutputFormat::Plain);
+    assert_eq!(runner.timeout, Some(Duration::from_secs(5)));
+    assert_eq!(runner.get_threads(), 4);
+    assert_eq!(runner.get_parameter("days"), Some(256));
+
+    assert!(matches!(
+      create_runner(&["--param", "window=3"]),
+      Err(ConfigError::UnknownParameter { .. })
+    ));
+
+    fs::remove_file(&path)?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_rejects_out_of_range_parameters() {
+    let opt = RunnerOpt::from_iter_safe(["day1", "-p", "part1", "--param", "window=0"])
+      .expect("invalid runner options");
+
+    assert!(matches!(
+      Runner::new("day1", opt),
+      Err(ConfigError::InvalidParameter {
+        value: 0,
+        min: 1,
+        ..
+      })
+    ));
+  }
+}
@@ -0,0 +1,192 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{BufRead, BufReader, Read};
+use std::str::FromStr;
+
+use serde::Serialize;
+use strum::EnumString;
+
+use aoc_core::{InputError, ParseError, ValidationError};
+
+const CSV_HEADER: &str = "timestamp,sensor,depth";
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum GapPolicy {
+  Interpolate,
+  Hold,
+  Skip,
+}
+
+struct Sample {
+  timestamp: u64,
+  sensor: String,
+  depth: usize,
+}
+
+impl FromStr for Sample {
+  type Err = ParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let fields = s.split(',').map(str::trim).collect::<Vec<_>>();
+
+    match fields[..] {
+      [timestamp, sensor, depth] if !sensor.is_empty() => Ok(Self {
+        timestamp: timestamp.parse()?,
+        sensor: sensor.to_string(),
+        depth: depth.parse()?,
+      }),
+      _ => Err(ParseError::Malformed {
+        kind: "sample",
+        value: s.to_string(),
+      }),
+    }
+  }
+}
+
+// Depths recorded by each sensor, sorted by timestamp.
+pub struct SonarLog(BTreeMap<String, Vec<(u64, usize)>>);
+
+impl SonarLog {
+  pub fn from_reader(reader: impl Read) -> Result<Self, InputError> {
+    let mut channels = BTreeMap::<_, Vec<_>>::new();
+
+    for (i, line) in BufReader::new(reader).lines().enumerate() {
+      let line = line?;
+      let line = line.trim();
+
+      if line.is_empty() || (i == 0 && line == CSV_HEADER) {
+        continue;
+      }
+
+      let sample = line.parse::<Sample>().map_err(|source| InputError::Line {
+        line: i + 1,
+        source,
+      })?;
+
+      channels
+        .entry(sample.sensor)
+        .or_default()
+        .push((sample.timestamp, sample.depth));
+    }
+
+    if channels.is_empty() {
+      return Err(ValidationError::Empty { kind: "sonar log" }.into());
+    }
+
+    for samples in channels.values_mut() {
+      // the last sample wins when a sensor reports twice for the same timestamp
+      samples.reverse();
+      samples.sort_by_key(|(timestamp, _)| *timestamp);
+      samples.dedup_by_key(|(timestamp, _)| *timestamp);
+    }
+
+    Ok(Self(channels))
+  }
+
+  pub fn get_channels(&self) -> impl Iterator<Item = (&str, &[(u64, usize)])> {
+    self
+      .0
+      .iter()
+      .map(|(sensor, samples)| (sensor.as_str(), samples.as_slice()))
+  }
+}
+
+// Largest grid the interpolate and hold policies lay out for a single channel.
+const MAX_GRID_SIZE: u64 = 10_000_000;
+
+// Lays the samples out on a regular grid of `period` starting at the first timestamp, filling the
+// missing timestamps according to the gap policy.
+pub fn resample(
+  samples: &[(u64, usize)],
+  period: u64,
+  policy: GapPolicy,
+) -> Result<Vec<usize>, ValidationError> {
+  let (start, end) = match (samples.first(), samples.last()) {
+    (Some((start, _)), Some((end, _))) => (*start, *end),
+    _ => return Ok(vec![]),
+  };
+
+  let period = period.max(1);
+
+  // skipping gaps keeps only the samples on the grid, however sparse they are
+  if policy == GapPolicy::Skip {
+    return Ok(
+      samples
+        .iter()
+        .filter(|(timestamp, _)| (timestamp - start) % period == 0)
+        .map(|(_, depth)| *depth)
+        .collect(),
+    );
+  }
+
+  let size = (end - start) / period + 1;
+
+  if size > MAX_GRID_SIZE {
+    return Err(ValidationError::TooLarge {
+      kind: "resampling grid",
+      max: MAX_GRID_SIZE,
+      actual: size,
+    });
+  }
+
+  let mut depths = Vec::with_capacity(size as usize);
+  depths.push(samples[0].1);
+
+  for pair in samples.windows(2) {
+    let ((previous_timestamp, previous_depth), (next_timestamp, next_depth)) = (pair[0], pair[1]);
+    // first grid timestamp after the previous sample
+    let mut timestamp = start + ((previous_timestamp - start) / period + 1) * period;
+
+    while timestamp < next_timestamp {
+      let depth = match policy {
+        GapPolicy::Interpolate => {
+          let ratio =
+            (timestamp - previous_timestamp) as f64 / (next_timestamp - previous_timestamp) as f64;
+
+          (previous_depth as f64 + (next_depth as f64 - previous_depth as f64) * ratio).round()
+            as usize
+        }
+        _ => previous_depth,
+      };
+
+      depths.push(depth);
+      timestamp += period;
+    }
+
+    if timestamp == next_timestamp {
+      depths.push(next_depth);
+    }
+  }
+
+  Ok(depths)
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChannelAnalysis {
+  pub sensor: String,
+  pub samples: usize,
+  pub increases: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct ChannelReport(pub Vec<ChannelAnalysis>);
+
+impl fmt::Display for ChannelReport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, analysis) in self.0.iter().enumerate() {
+      if i > 0 {
+        writeln!(f)?;
+      }
+
+      write!(
+        f,
+        "{}: {} increase(s) over {} sample(s)",
+        analysis.sensor, analysis.increases, analysis.samples
+      )?;
+    }
+
+    Ok(())
+  }
+}
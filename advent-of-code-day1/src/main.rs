@@ -1,96 +1,370 @@
-use std::fs::File;
+mod channel;
+mod plot;
+mod report;
+mod sonar;
+mod window;
+
+use std::fs;
+use std::io::BufRead;
 use std::path::PathBuf;
 
+use aoc_core::{Puzzle, Result, Runner, RunnerOpt};
 use structopt::StructOpt;
 
-use aoc_core::{Puzzle, Result};
+use channel::{ChannelAnalysis, ChannelReport, GapPolicy, SonarLog};
+use plot::{DepthProfile, Series};
+use report::{TrendReport, TrendReporter};
+use sonar::{DepthReader, SonarAnalyzer};
+use window::{Aggregate, SlidingWindow, Window};
 
 #[derive(StructOpt)]
 struct Opt {
+  #[structopt(flatten)]
+  runner: RunnerOpt,
   #[structopt(short, long)]
-  filename: PathBuf,
-  #[structopt(short, long)]
-  puzzle: Puzzle,
-  #[structopt(short, long)]
   debug: bool,
+  /// Print the running count every time a new measurement comes in, e.g. with `-f -`
+  #[structopt(long)]
+  follow: bool,
+  /// Sum, mean, median, min, max or ema
+  #[structopt(long, default_value = "sum")]
+  aggregate: Aggregate,
+  #[structopt(long, default_value = "0")]
+  tolerance: f64,
+  /// Split the sweep into ascents and descents and flag outliers instead of counting increases
+  #[structopt(long)]
+  report: bool,
+  #[structopt(long, default_value = "10")]
+  outlier_window: usize,
+  #[structopt(long, default_value = "3")]
+  z_threshold: f64,
+  /// Read `timestamp,sensor,depth` records and analyse each sensor on its own
+  #[structopt(long)]
+  channels: bool,
+  /// Interpolate, hold or skip the missing timestamps of a channel
+  #[structopt(long, default_value = "interpolate")]
+  gaps: GapPolicy,
+  /// Chart the raw and smoothed depths in the terminal
+  #[structopt(long)]
+  plot: bool,
+  /// Also write the chart as an SVG file
+  #[structopt(long, parse(from_os_str))]
+  svg: Option<PathBuf>,
+}
+
+fn get_window(puzzle: Puzzle) -> Window {
+  match puzzle {
+    Puzzle::Part1 => Window::new(1),
+    Puzzle::Part2 => Window::new(3),
+  }
+}
+
+fn solve_puzzle(
+  reader: impl BufRead,
+  mut analyzer: SonarAnalyzer,
+  debug: bool,
+  follow: bool,
+) -> Result<usize> {
+  for depth in DepthReader::new(reader) {
+    let measurement = match analyzer.push(depth?) {
+      Some(measurement) => measurement,
+      None => continue,
+    };
+
+    if debug {
+      println!("{} ({})", measurement.depth, measurement.trend);
+    }
+
+    if follow {
+      println!("{} increase(s) so far", measurement.increases);
+    }
+  }
+
+  Ok(analyzer.get_increases())
 }
 
-fn solve_puzzle(depths: Vec<usize>, puzzle: Puzzle, debug: bool) -> usize {
-  let depths = match puzzle {
-    Puzzle::Part1 => depths,
-    Puzzle::Part2 => depths
-      .windows(3)
-      .map(|depths| depths.iter().sum())
-      .collect(),
+fn generate_report(
+  reader: impl BufRead,
+  mut analyzer: SonarAnalyzer,
+  mut reporter: TrendReporter,
+) -> Result<TrendReport> {
+  for depth in DepthReader::new(reader) {
+    if let Some(measurement) = analyzer.push(depth?) {
+      reporter.push(&measurement);
+    }
+  }
+
+  Ok(reporter.finish())
+}
+
+fn plot_profile(reader: impl BufRead) -> Result<DepthProfile> {
+  let mut raw = SlidingWindow::new(Window::new(1));
+  let mut smoothed = SlidingWindow::new(Window {
+    aggregate: Aggregate::Mean,
+    ..Window::new(3)
+  });
+  let mut profile = DepthProfile {
+    raw: Series::new("raw depths"),
+    smoothed: Series::new("3-measurement mean"),
   };
 
-  let (_, counter) = depths
-    .iter()
-    .fold((None, 0), |(previous_depth, counter), depth| {
-      let (message, counter) = match previous_depth {
-        None => ("N/A - previous measurement", 0),
-        Some(previous_depth) if depth > previous_depth => ("increased", counter + 1),
-        _ => ("decreased", counter),
-      };
+  for depth in DepthReader::new(reader) {
+    let depth = depth?;
 
-      if debug {
-        println!("{} ({})", depth, message);
+    profile.raw.depths.extend(raw.push(depth));
+    profile.smoothed.depths.extend(smoothed.push(depth));
+  }
+
+  Ok(profile)
+}
+
+fn analyze_channels(
+  log: SonarLog,
+  period: u64,
+  gaps: GapPolicy,
+  create_analyzer: impl Fn() -> SonarAnalyzer,
+) -> Result<ChannelReport> {
+  let analyses = log
+    .get_channels()
+    .map(|(sensor, samples)| {
+      let depths = channel::resample(samples, period, gaps)?;
+      let mut analyzer = create_analyzer();
+
+      for depth in depths.iter() {
+        analyzer.push(*depth);
       }
 
-      (Some(depth), counter)
-    });
+      Ok(ChannelAnalysis {
+        sensor: sensor.to_string(),
+        samples: depths.len(),
+        increases: analyzer.get_increases(),
+      })
+    })
+    .collect::<Result<_>>()?;
 
-  counter
+  Ok(ChannelReport(analyses))
 }
 
 fn main() -> Result<()> {
   color_eyre::install()?;
 
   let opt = Opt::from_args();
-  let depths = aoc_core::read_lines(File::open(opt.filename)?)?;
-  let result = solve_puzzle(depths, opt.puzzle, opt.debug);
+  let debug = opt.debug;
+  let follow = opt.follow;
+  let aggregate = opt.aggregate;
+  let tolerance = opt.tolerance;
+  let runner = Runner::new("day1", opt.runner)?;
+  let size = runner.get_parameter("window");
+  let stride = runner.get_parameter("stride");
+  let period = runner.get_parameter("period").unwrap_or(1) as u64;
+
+  let create_analyzer = move |puzzle| {
+    let default_window = get_window(puzzle);
+    let window = Window {
+      size: size.unwrap_or(default_window.size),
+      stride: stride.unwrap_or(default_window.stride),
+      aggregate,
+    };
+
+    SonarAnalyzer::new(window, tolerance)
+  };
+
+  if opt.plot || opt.svg.is_some() {
+    let svg = opt.svg;
 
-  println!("{}", result);
+    runner.run_reader(move |reader, _| {
+      let profile = plot_profile(reader)?;
 
-  Ok(())
+      if let Some(path) = svg {
+        fs::write(path, profile.render_svg())?;
+      }
+
+      Ok(profile)
+    })
+  } else if opt.channels {
+    let gaps = opt.gaps;
+
+    runner.run_reader(move |reader, puzzle| {
+      let log = SonarLog::from_reader(reader)?;
+
+      analyze_channels(log, period, gaps, || create_analyzer(puzzle))
+    })
+  } else if opt.report {
+    let reporter = TrendReporter::new(opt.outlier_window, opt.z_threshold);
+
+    runner
+      .run_reader(move |reader, puzzle| generate_report(reader, create_analyzer(puzzle), reporter))
+  } else {
+    runner.run_reader(move |reader, puzzle| {
+      solve_puzzle(reader, create_analyzer(puzzle), debug, follow)
+    })
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
-  const EXAMPLE_DATA: &str = r"199
-200
-208
-210
-200
-207
-240
-269
-260
-263
-";
-
-  const EXAMPLE_RESPONSE_PART1: usize = 7;
-  const EXAMPLE_RESPONSE_PART2: usize = 5;
+  use sonar::Trend;
+
+  aoc_test::example_tests!(|input: &str, puzzle| {
+    let analyzer = SonarAnalyzer::new(get_window(puzzle), 0.0);
+
+    solve_puzzle(input.as_bytes(), analyzer, true, true)
+  });
 
   #[test]
-  fn it_solves_example_input_part1() -> Result<()> {
-    let depths = aoc_core::read_lines(EXAMPLE_DATA.as_bytes())?;
-    let counter = solve_puzzle(depths, Puzzle::Part1, true);
+  fn it_reports_invalid_depth_line() {
+    let analyzer = SonarAnalyzer::new(Window::new(1), 0.0);
+    let error = solve_puzzle("199\n200\n\nabc\n".as_bytes(), analyzer, false, false)
+      .unwrap_err()
+      .to_string();
 
-    assert_eq!(EXAMPLE_RESPONSE_PART1, counter);
+    assert_eq!(error, "invalid input at line 4");
+  }
+
+  #[test]
+  fn it_labels_flat_readings_unchanged() {
+    let mut analyzer = SonarAnalyzer::new(Window::new(1), 2.0);
+    let trends = [100, 100, 102, 105, 104, 101, 101]
+      .iter()
+      .filter_map(|depth| analyzer.push(*depth))
+      .map(|measurement| measurement.trend)
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      trends,
+      vec![
+        Trend::Unknown,
+        Trend::Unchanged,
+        Trend::Unchanged,
+        Trend::Increased,
+        Trend::Unchanged,
+        Trend::Decreased,
+        Trend::Unchanged,
+      ]
+    );
+    assert_eq!(analyzer.get_increases(), 1);
+  }
+
+  #[test]
+  fn it_aggregates_strided_windows() -> Result<()> {
+    let input = aoc_test::example!("example").input;
+    let count_increases = |aggregate, stride, tolerance| {
+      let window = Window {
+        size: 3,
+        stride,
+        aggregate,
+      };
+
+      solve_puzzle(
+        input.as_bytes(),
+        SonarAnalyzer::new(window, tolerance),
+        false,
+        false,
+      )
+    };
+
+    assert_eq!(count_increases(Aggregate::Sum, 1, 0.0)?, 5);
+    assert_eq!(count_increases(Aggregate::Sum, 1, 25.0)?, 3);
+    assert_eq!(count_increases(Aggregate::Median, 1, 0.0)?, 4);
+    assert_eq!(count_increases(Aggregate::Min, 3, 0.0)?, 2);
+    assert_eq!(count_increases(Aggregate::Max, 2, 0.0)?, 3);
 
     Ok(())
   }
 
   #[test]
-  fn it_solves_examples_input_part2() -> Result<()> {
-    let depths = aoc_core::read_lines(EXAMPLE_DATA.as_bytes())?;
-    let counter = solve_puzzle(depths, Puzzle::Part2, true);
+  fn it_renders_trend_report() -> Result<()> {
+    let report = generate_report(
+      aoc_test::example!("example").input.as_bytes(),
+      SonarAnalyzer::new(Window::new(1), 0.0),
+      TrendReporter::new(3, 3.0),
+    )?;
 
-    assert_eq!(EXAMPLE_RESPONSE_PART2, counter);
+    insta::assert_snapshot!(aoc_test::render(&report));
 
     Ok(())
   }
+
+  #[test]
+  fn it_renders_depth_profile() -> Result<()> {
+    let profile = plot_profile(aoc_test::example!("example").input.as_bytes())?;
+
+    insta::assert_snapshot!(aoc_test::render(&profile));
+    insta::assert_snapshot!(profile.render_svg());
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_analyzes_each_sensor_channel() -> Result<()> {
+    let input = "timestamp,sensor,depth\n\
+      0,a,100\n\
+      0,b,300\n\
+      1,a,110\n\
+      4,a,90\n\
+      2,b,290\n\
+      3,b,280\n\
+      3,b,310\n";
+    let count_increases = |gaps| -> Result<Vec<(String, usize, usize)>> {
+      let log = SonarLog::from_reader(input.as_bytes())?;
+      let report = analyze_channels(log, 1, gaps, || SonarAnalyzer::new(Window::new(1), 0.0))?;
+
+      Ok(
+        report
+          .0
+          .into_iter()
+          .map(|analysis| (analysis.sensor, analysis.samples, analysis.increases))
+          .collect(),
+      )
+    };
+
+    // a: 100 110 [103 97] 90, b: 300 [295] 290 310
+    assert_eq!(
+      count_increases(GapPolicy::Interpolate)?,
+      vec![("a".to_string(), 5, 1), ("b".to_string(), 4, 1)]
+    );
+    // a: 100 110 [110 110] 90, b: 300 [300] 290 310
+    assert_eq!(
+      count_increases(GapPolicy::Hold)?,
+      vec![("a".to_string(), 5, 1), ("b".to_string(), 4, 1)]
+    );
+    assert_eq!(
+      count_increases(GapPolicy::Skip)?,
+      vec![("a".to_string(), 3, 1), ("b".to_string(), 3, 1)]
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_resamples_sparse_channels() -> Result<()> {
+    let input = "0,a,100\n1000000000000,a,110\n";
+    let analyze = |gaps| -> Result<ChannelReport> {
+      let log = SonarLog::from_reader(input.as_bytes())?;
+
+      analyze_channels(log, 1, gaps, || SonarAnalyzer::new(Window::new(1), 0.0))
+    };
+
+    let report = analyze(GapPolicy::Skip)?;
+    assert_eq!((report.0[0].samples, report.0[0].increases), (2, 1));
+
+    let error = analyze(GapPolicy::Interpolate).err().map(|e| e.to_string());
+    assert_eq!(
+      error.as_deref(),
+      Some("resampling grid too large (1000000000001 over the 10000000 limit)")
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_reports_invalid_sample_line() {
+    let error = SonarLog::from_reader("0,a,100\n1,a\n".as_bytes())
+      .err()
+      .map(|e| e.to_string());
+
+    assert_eq!(error.as_deref(), Some("invalid input at line 2"));
+  }
 }
@@ -0,0 +1,197 @@
+use std::fmt::{self, Write};
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::sonar::Trend;
+
+const CHART_WIDTH: usize = 80;
+const CHART_HEIGHT: usize = 10;
+const SVG_WIDTH: f64 = 800.0;
+const SVG_PANEL_HEIGHT: f64 = 200.0;
+const SVG_MARGIN: f64 = 20.0;
+
+#[derive(Debug, Serialize)]
+pub struct Series {
+  pub label: &'static str,
+  pub depths: Vec<f64>,
+}
+
+impl Series {
+  pub fn new(label: &'static str) -> Self {
+    Self {
+      label,
+      depths: vec![],
+    }
+  }
+
+  fn get_range(&self) -> (f64, f64) {
+    self
+      .depths
+      .iter()
+      .fold((f64::MAX, f64::MIN), |(min, max), depth| {
+        (min.min(*depth), max.max(*depth))
+      })
+  }
+
+  // Averages the depths into at most `width` columns so that long sweeps fit the terminal.
+  fn get_columns(&self, width: usize) -> Vec<f64> {
+    let count = self.depths.len();
+    let width = width.min(count);
+
+    (0..width)
+      .map(|i| {
+        let bucket = &self.depths[i * count / width..(i + 1) * count / width];
+
+        bucket.iter().sum::<f64>() / bucket.len() as f64
+      })
+      .collect()
+  }
+}
+
+// Same labelling as the debug output without any tolerance.
+fn get_trend(depths: &[f64], i: usize) -> Trend {
+  match i.checked_sub(1).map(|previous| depths[previous]) {
+    None => Trend::Unknown,
+    Some(previous_depth) if depths[i] > previous_depth => Trend::Increased,
+    Some(previous_depth) if depths[i] < previous_depth => Trend::Decreased,
+    Some(_) => Trend::Unchanged,
+  }
+}
+
+fn get_svg_color(trend: Trend) -> &'static str {
+  match trend {
+    Trend::Unknown => "gray",
+    Trend::Increased => "green",
+    Trend::Decreased => "red",
+    Trend::Unchanged => "blue",
+  }
+}
+
+fn get_scale(min: f64, max: f64) -> f64 {
+  if max > min {
+    1.0 / (max - min)
+  } else {
+    0.0
+  }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DepthProfile {
+  pub raw: Series,
+  pub smoothed: Series,
+}
+
+impl DepthProfile {
+  pub fn render_svg(&self) -> String {
+    let height = SVG_PANEL_HEIGHT * 2.0;
+    let mut svg = String::new();
+
+    writeln!(
+      svg,
+      r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+      SVG_WIDTH, height
+    )
+    .unwrap();
+
+    for (i, series) in [&self.raw, &self.smoothed].iter().enumerate() {
+      let top = SVG_PANEL_HEIGHT * i as f64;
+      let (min, max) = series.get_range();
+      let scale = get_scale(min, max);
+      let step =
+        (SVG_WIDTH - SVG_MARGIN * 2.0) / series.depths.len().saturating_sub(1).max(1) as f64;
+      let get_point = |i: usize| {
+        (
+          SVG_MARGIN + step * i as f64,
+          // deeper measurements are drawn lower
+          top
+            + SVG_MARGIN * 1.5
+            + (series.depths[i] - min) * scale * (SVG_PANEL_HEIGHT - SVG_MARGIN * 2.5),
+        )
+      };
+
+      writeln!(
+        svg,
+        r#"  <text x="{}" y="{}" font-family="monospace" font-size="12">{}</text>"#,
+        SVG_MARGIN,
+        top + SVG_MARGIN,
+        series.label
+      )
+      .unwrap();
+
+      for i in 1..series.depths.len() {
+        let (x1, y1) = get_point(i - 1);
+        let (x2, y2) = get_point(i);
+
+        writeln!(
+          svg,
+          r#"  <line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}"/>"#,
+          x1,
+          y1,
+          x2,
+          y2,
+          get_svg_color(get_trend(&series.depths, i))
+        )
+        .unwrap();
+      }
+    }
+
+    svg.push_str("</svg>\n");
+
+    svg
+  }
+}
+
+impl fmt::Display for Series {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} ({} measurements)", self.label, self.depths.len())?;
+
+    let columns = self.get_columns(CHART_WIDTH);
+    let (min, max) = self.get_range();
+    let scale = get_scale(min, max);
+    let rows = columns
+      .iter()
+      .map(|depth| ((depth - min) * scale * (CHART_HEIGHT - 1) as f64).round() as usize)
+      .collect::<Vec<_>>();
+
+    if columns.is_empty() {
+      return Ok(());
+    }
+
+    for row in 0..CHART_HEIGHT {
+      let axis = match row {
+        0 => format!("{:.0}", min),
+        row if row == CHART_HEIGHT - 1 => format!("{:.0}", max),
+        _ => String::new(),
+      };
+
+      let mut line = format!("{:>8} |", axis);
+
+      for (i, column_row) in rows.iter().enumerate() {
+        if *column_row != row {
+          line.push(' ');
+          continue;
+        }
+
+        let point = match get_trend(&columns, i) {
+          Trend::Unknown => "*".normal(),
+          Trend::Increased => "*".green(),
+          Trend::Decreased => "*".red(),
+          Trend::Unchanged => "*".blue(),
+        };
+
+        write!(line, "{}", point)?;
+      }
+
+      write!(f, "\n{}", line.trim_end())?;
+    }
+
+    Ok(())
+  }
+}
+
+impl fmt::Display for DepthProfile {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}\n\n{}", self.raw, self.smoothed)
+  }
+}
@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use serde::Serialize;
+use strum::Display;
+
+use crate::sonar::{Measurement, Trend};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum Direction {
+  Ascent,
+  Descent,
+}
+
+// A run spans every measurement from `start` to `end` (both included), so two consecutive runs
+// share their boundary measurement.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Run {
+  pub direction: Direction,
+  pub start: usize,
+  pub end: usize,
+  pub length: usize,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize)]
+pub struct Outlier {
+  pub index: usize,
+  pub depth: f64,
+  pub z_score: f64,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct TrendReport {
+  pub runs: Vec<Run>,
+  pub longest_ascent: Option<Run>,
+  pub longest_descent: Option<Run>,
+  pub outliers: Vec<Outlier>,
+}
+
+impl fmt::Display for TrendReport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(
+      f,
+      "{:<10} {:>8} {:>8} {:>8}",
+      "direction", "start", "end", "length"
+    )?;
+
+    for run in self.runs.iter() {
+      writeln!(
+        f,
+        "{:<10} {:>8} {:>8} {:>8}",
+        run.direction, run.start, run.end, run.length
+      )?;
+    }
+
+    for (label, run) in [
+      ("longest ascent", self.longest_ascent),
+      ("longest descent", self.longest_descent),
+    ] {
+      match run {
+        Some(run) => writeln!(f, "{}: {}..={} ({})", label, run.start, run.end, run.length)?,
+        None => writeln!(f, "{}: none", label)?,
+      }
+    }
+
+    write!(f, "{} outlier(s)", self.outliers.len())?;
+
+    for outlier in self.outliers.iter() {
+      write!(
+        f,
+        "\n{:>8} {:>10} {:>8.2}",
+        outlier.index, outlier.depth, outlier.z_score
+      )?;
+    }
+
+    Ok(())
+  }
+}
+
+pub struct TrendReporter {
+  // outliers are depths too far from the mean of the `outlier_window` previous ones
+  outlier_window: usize,
+  z_threshold: f64,
+  recent_depths: VecDeque<f64>,
+  index: usize,
+  run: Option<Run>,
+  report: TrendReport,
+}
+
+impl TrendReporter {
+  pub fn new(outlier_window: usize, z_threshold: f64) -> Self {
+    Self {
+      outlier_window: outlier_window.max(1),
+      z_threshold,
+      recent_depths: VecDeque::with_capacity(outlier_window),
+      index: 0,
+      run: None,
+      report: TrendReport::default(),
+    }
+  }
+
+  pub fn push(&mut self, measurement: &Measurement) {
+    let index = self.index;
+
+    self.index += 1;
+    self.detect_outlier(index, measurement.depth);
+
+    let direction = match measurement.trend {
+      Trend::Unknown => return,
+      Trend::Increased => Direction::Ascent,
+      Trend::Decreased => Direction::Descent,
+      // a flat reading ends the current run without starting a new one
+      Trend::Unchanged => return self.close_run(),
+    };
+
+    match self.run.as_mut() {
+      Some(run) if run.direction == direction => {
+        run.end = index;
+        run.length += 1;
+      }
+      _ => {
+        self.close_run();
+        self.run = Some(Run {
+          direction,
+          start: index - 1,
+          end: index,
+          length: 2,
+        });
+      }
+    }
+  }
+
+  pub fn finish(mut self) -> TrendReport {
+    self.close_run();
+
+    self.report
+  }
+
+  fn detect_outlier(&mut self, index: usize, depth: f64) {
+    if self.recent_depths.len() == self.outlier_window {
+      let size = self.recent_depths.len() as f64;
+      let mean = self.recent_depths.iter().sum::<f64>() / size;
+      let variance = self
+        .recent_depths
+        .iter()
+        .map(|depth| (depth - mean).powi(2))
+        .sum::<f64>()
+        / size;
+      let deviation = variance.sqrt();
+
+      // a flat window has no spread to measure against
+      if deviation > 0.0 {
+        let z_score = (depth - mean) / deviation;
+
+        if z_score.abs() > self.z_threshold {
+          self.report.outliers.push(Outlier {
+            index,
+            depth,
+            z_score,
+          });
+        }
+      }
+
+      self.recent_depths.pop_front();
+    }
+
+    self.recent_depths.push_back(depth);
+  }
+
+  fn close_run(&mut self) {
+    let run = match self.run.take() {
+      Some(run) => run,
+      None => return,
+    };
+
+    let longest_run = match run.direction {
+      Direction::Ascent => &mut self.report.longest_ascent,
+      Direction::Descent => &mut self.report.longest_descent,
+    };
+
+    if longest_run.map_or(true, |longest_run| run.length > longest_run.length) {
+      *longest_run = Some(run);
+    }
+
+    self.report.runs.push(run);
+  }
+}
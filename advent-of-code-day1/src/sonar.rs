@@ -0,0 +1,119 @@
+use std::fmt;
+use std::io::BufRead;
+
+use aoc_core::{InputError, ParseError};
+
+use crate::window::{SlidingWindow, Window};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Trend {
+  Unknown,
+  Increased,
+  Decreased,
+  Unchanged,
+}
+
+impl fmt::Display for Trend {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Unknown => write!(f, "N/A - previous measurement"),
+      Self::Increased => write!(f, "increased"),
+      Self::Decreased => write!(f, "decreased"),
+      Self::Unchanged => write!(f, "no change"),
+    }
+  }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Measurement {
+  pub depth: f64,
+  pub trend: Trend,
+  pub increases: usize,
+}
+
+pub struct SonarAnalyzer {
+  window: SlidingWindow,
+  // how much a depth has to move away from the previous one to count as an increase or a decrease
+  tolerance: f64,
+  previous_depth: Option<f64>,
+  increases: usize,
+}
+
+impl SonarAnalyzer {
+  pub fn new(window: Window, tolerance: f64) -> Self {
+    Self {
+      window: SlidingWindow::new(window),
+      tolerance,
+      previous_depth: None,
+      increases: 0,
+    }
+  }
+
+  pub fn push(&mut self, depth: usize) -> Option<Measurement> {
+    let depth = self.window.push(depth)?;
+
+    let trend = match self.previous_depth {
+      None => Trend::Unknown,
+      Some(previous_depth) if depth - previous_depth > self.tolerance => {
+        self.increases += 1;
+
+        Trend::Increased
+      }
+      Some(previous_depth) if previous_depth - depth > self.tolerance => Trend::Decreased,
+      Some(_) => Trend::Unchanged,
+    };
+
+    self.previous_depth = Some(depth);
+
+    Some(Measurement {
+      depth,
+      trend,
+      increases: self.increases,
+    })
+  }
+
+  pub fn get_increases(&self) -> usize {
+    self.increases
+  }
+}
+
+pub struct DepthReader<R> {
+  reader: R,
+  buffer: String,
+  line: usize,
+}
+
+impl<R: BufRead> DepthReader<R> {
+  pub fn new(reader: R) -> Self {
+    Self {
+      reader,
+      buffer: String::new(),
+      line: 0,
+    }
+  }
+}
+
+impl<R: BufRead> Iterator for DepthReader<R> {
+  type Item = Result<usize, InputError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      self.buffer.clear();
+
+      match self.reader.read_line(&mut self.buffer) {
+        Ok(0) => return None,
+        Ok(_) => self.line += 1,
+        Err(e) => return Some(Err(e.into())),
+      }
+
+      let depth = self.buffer.trim();
+
+      if !depth.is_empty() {
+        return Some(depth.parse::<usize>().map_err(|e| InputError::Line {
+          line: self.line,
+          source: ParseError::from(e),
+        }));
+      }
+    }
+  }
+}
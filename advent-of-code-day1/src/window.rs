@@ -0,0 +1,117 @@
+use std::collections::VecDeque;
+
+use strum::EnumString;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Aggregate {
+  Sum,
+  Mean,
+  Median,
+  Min,
+  Max,
+  Ema,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Window {
+  pub size: usize,
+  pub stride: usize,
+  pub aggregate: Aggregate,
+}
+
+impl Window {
+  pub fn new(size: usize) -> Self {
+    Self {
+      size: size.max(1),
+      stride: 1,
+      aggregate: Aggregate::Sum,
+    }
+  }
+}
+
+// Keeps the last `size` depths and aggregates them every `stride` depths once the window is full.
+pub struct SlidingWindow {
+  window: Window,
+  depths: VecDeque<usize>,
+  since_report: Option<usize>,
+  // the moving average runs over every depth, seeded with the mean of the first full window
+  ema: Option<f64>,
+}
+
+impl SlidingWindow {
+  pub fn new(window: Window) -> Self {
+    let window = Window {
+      size: window.size.max(1),
+      stride: window.stride.max(1),
+      ..window
+    };
+
+    Self {
+      window,
+      depths: VecDeque::with_capacity(window.size),
+      since_report: None,
+      ema: None,
+    }
+  }
+
+  pub fn push(&mut self, depth: usize) -> Option<f64> {
+    if self.depths.len() == self.window.size {
+      self.depths.pop_front();
+    }
+
+    self.depths.push_back(depth);
+
+    if self.depths.len() < self.window.size {
+      return None;
+    }
+
+    let alpha = 2.0 / (self.window.size as f64 + 1.0);
+
+    self.ema = Some(match self.ema {
+      Some(ema) => alpha * depth as f64 + (1.0 - alpha) * ema,
+      None => self.get_sum() / self.window.size as f64,
+    });
+
+    // the first full window is always reported
+    if let Some(count) = self.since_report.as_mut() {
+      *count += 1;
+
+      if *count < self.window.stride {
+        return None;
+      }
+    }
+
+    self.since_report = Some(0);
+
+    Some(self.aggregate())
+  }
+
+  fn aggregate(&self) -> f64 {
+    let size = self.depths.len() as f64;
+
+    match self.window.aggregate {
+      Aggregate::Sum => self.get_sum(),
+      Aggregate::Mean => self.get_sum() / size,
+      Aggregate::Median => {
+        let mut depths = self.depths.iter().copied().collect::<Vec<_>>();
+        let middle = depths.len() / 2;
+
+        depths.sort_unstable();
+
+        if depths.len() % 2 == 0 {
+          (depths[middle - 1] + depths[middle]) as f64 / 2.0
+        } else {
+          depths[middle] as f64
+        }
+      }
+      Aggregate::Min => self.depths.iter().min().copied().unwrap_or_default() as f64,
+      Aggregate::Max => self.depths.iter().max().copied().unwrap_or_default() as f64,
+      Aggregate::Ema => self.ema.unwrap_or_default(),
+    }
+  }
+
+  fn get_sum(&self) -> f64 {
+    self.depths.iter().sum::<usize>() as f64
+  }
+}
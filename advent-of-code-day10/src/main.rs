@@ -1,19 +1,15 @@
 mod parser;
 
-use std::fs::File;
-use std::path::PathBuf;
 use structopt::StructOpt;
 
-use aoc_core::{Puzzle, Result};
+use aoc_core::{Puzzle, Result, Runner, RunnerOpt};
 
 use parser::Parser;
 
 #[derive(StructOpt)]
 struct Opt {
-  #[structopt(short, long)]
-  filename: PathBuf,
-  #[structopt(short, long)]
-  puzzle: Puzzle,
+  #[structopt(flatten)]
+  runner: RunnerOpt,
 }
 
 fn solve_puzzle(parser: Parser, puzzle: Puzzle) -> usize {
@@ -28,49 +24,20 @@ fn main() -> Result<()> {
 
   let opt = Opt::from_args();
 
-  let parser = Parser::from_reader(File::open(opt.filename)?)?;
-  let result = solve_puzzle(parser, opt.puzzle);
+  Runner::new("day10", opt.runner)?.run(move |input, puzzle| {
+    let parser = Parser::from_reader(&input[..])?;
 
-  println!("{}", result);
-
-  Ok(())
+    Ok(solve_puzzle(parser, puzzle))
+  })
 }
 
 #[cfg(test)]
 mod test {
   use super::*;
 
-  const EXAMPLE_DATA: &str = r"[({(<(())[]>[[{[]{<()<>>
-[(()[<>])]({[<{<<[]>>(
-{([(<{}[<>[]}>{[]{[(<()>
-(((({<>}<{<{<>}{[]{[]{}
-[[<[([]))<([[{}[[()]]]
-[{[{({}]{}}([{[{{{}}([]
-{<[[]]>}<{[{[{[]{()[[[]
-[<(<(<(<{}))><([]([]()
-<{([([[(<>()){}]>(<<{{
-<{([{{}}[<[[[<>{}]]]>[]]";
-
-  const EXAMPLE_RESPONSE_PART1: usize = 26397;
-  const EXAMPLE_RESPONSE_PART2: usize = 288957;
-
-  #[test]
-  fn it_solves_example_input_part1() -> Result<()> {
-    let parser = Parser::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(parser, Puzzle::Part1);
+  aoc_test::example_tests!(|input: &str, puzzle| {
+    let parser = Parser::from_reader(input.as_bytes())?;
 
-    assert_eq!(EXAMPLE_RESPONSE_PART1, result);
-
-    Ok(())
-  }
-
-  #[test]
-  fn it_solves_example_input_part2() -> Result<()> {
-    let parser = Parser::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(parser, Puzzle::Part2);
-
-    assert_eq!(EXAMPLE_RESPONSE_PART2, result);
-
-    Ok(())
-  }
+    Ok(solve_puzzle(parser, puzzle))
+  });
 }
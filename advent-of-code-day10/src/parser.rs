@@ -1,9 +1,8 @@
 use std::io::{BufRead, BufReader, Read};
 
-use eyre::eyre;
 use std::convert::TryFrom;
 
-use aoc_core::{Error, Result};
+use aoc_core::{InputError, ParseError};
 
 enum OpenChunkSymbol {
   OpenParenthesis,
@@ -24,15 +23,18 @@ impl OpenChunkSymbol {
 }
 
 impl TryFrom<char> for OpenChunkSymbol {
-  type Error = Error;
+  type Error = ParseError;
 
-  fn try_from(c: char) -> Result<Self> {
+  fn try_from(c: char) -> Result<Self, Self::Error> {
     match c {
       '(' => Ok(Self::OpenParenthesis),
       '[' => Ok(Self::OpenSquareBracket),
       '{' => Ok(Self::OpenBrace),
       '<' => Ok(Self::LessSign),
-      _ => Err(eyre!("Invalid '{}' open chunk symbol")),
+      _ => Err(ParseError::InvalidChar {
+        kind: "open chunk symbol",
+        value: c,
+      }),
     }
   }
 }
@@ -66,15 +68,18 @@ impl CloseChunkSymbol {
 }
 
 impl TryFrom<char> for CloseChunkSymbol {
-  type Error = Error;
+  type Error = ParseError;
 
-  fn try_from(c: char) -> Result<Self> {
+  fn try_from(c: char) -> Result<Self, Self::Error> {
     match c {
       ')' => Ok(Self::CloseParenthesis),
       ']' => Ok(Self::CloseSquareBracket),
       '}' => Ok(Self::CloseBrace),
       '>' => Ok(Self::GreaterSign),
-      _ => Err(eyre!("Invalid '{}' close chunk symbol")),
+      _ => Err(ParseError::InvalidChar {
+        kind: "close chunk symbol",
+        value: c,
+      }),
     }
   }
 }
@@ -82,7 +87,7 @@ impl TryFrom<char> for CloseChunkSymbol {
 enum Token {
   OpenChunk(OpenChunkSymbol),
   CloseChunk(CloseChunkSymbol),
-  Unclassified(char),
+  Unclassified,
 }
 
 impl From<char> for Token {
@@ -90,7 +95,7 @@ impl From<char> for Token {
     OpenChunkSymbol::try_from(c)
       .map(Self::OpenChunk)
       .or_else(|_| CloseChunkSymbol::try_from(c).map(Self::CloseChunk))
-      .unwrap_or_else(|_| Self::Unclassified(c))
+      .unwrap_or_else(|_| Self::Unclassified)
   }
 }
 
@@ -111,7 +116,7 @@ pub struct Parser {
 }
 
 impl Parser {
-  pub fn from_reader(reader: impl Read) -> Result<Self> {
+  pub fn from_reader(reader: impl Read) -> Result<Self, InputError> {
     let reader = BufReader::new(reader);
 
     let lines = reader
@@ -127,9 +132,9 @@ impl Parser {
               tokens,
             }
           })
-          .map_err(Into::into)
+          .map_err(InputError::from)
       })
-      .collect::<Result<Vec<_>>>()?;
+      .collect::<Result<Vec<_>, _>>()?;
 
     Ok(Self { lines })
   }
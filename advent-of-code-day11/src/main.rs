@@ -1,26 +1,27 @@
 mod simulator;
 
-use std::fs::File;
-use std::path::PathBuf;
 use structopt::StructOpt;
 
-use aoc_core::{Puzzle, Result};
+use aoc_core::{Puzzle, Result, Runner, RunnerOpt};
 
 use simulator::DumboOctopusSimulator;
 
 #[derive(StructOpt)]
 struct Opt {
-  #[structopt(short, long)]
-  filename: PathBuf,
-  #[structopt(short, long)]
-  puzzle: Puzzle,
+  #[structopt(flatten)]
+  runner: RunnerOpt,
   #[structopt(short, long)]
   debug: bool,
 }
 
-fn solve_puzzle(mut simulator: DumboOctopusSimulator, puzzle: Puzzle, debug: bool) -> usize {
+fn solve_puzzle(
+  mut simulator: DumboOctopusSimulator,
+  puzzle: Puzzle,
+  debug: bool,
+  steps: Option<usize>,
+) -> usize {
   match puzzle {
-    Puzzle::Part1 => simulator.simulate(100, debug),
+    Puzzle::Part1 => simulator.simulate(steps.unwrap_or(100), debug),
     Puzzle::Part2 => simulator.simulate_until_synchronization(debug),
   }
 }
@@ -29,49 +30,35 @@ fn main() -> Result<()> {
   color_eyre::install()?;
 
   let opt = Opt::from_args();
+  let debug = opt.debug;
+  let runner = Runner::new("day11", opt.runner)?;
+  let steps = runner.get_parameter("steps");
 
-  let simulator = DumboOctopusSimulator::from_reader(File::open(opt.filename)?)?;
-  let result = solve_puzzle(simulator, opt.puzzle, opt.debug);
-
-  println!("{}", result);
+  runner.run(move |input, puzzle| {
+    let simulator = DumboOctopusSimulator::from_reader(&input[..])?;
 
-  Ok(())
+    Ok(solve_puzzle(simulator, puzzle, debug, steps))
+  })
 }
 
 #[cfg(test)]
 mod test {
   use super::*;
 
-  const EXAMPLE_DATA: &str = r"5483143223
-2745854711
-5264556173
-6141336146
-6357385478
-4167524645
-2176841721
-6882881134
-4846848554
-5283751526";
+  aoc_test::example_tests!(|input: &str, puzzle| {
+    let simulator = DumboOctopusSimulator::from_reader(input.as_bytes())?;
 
-  const EXAMPLE_RESPONSE_PART1: usize = 1656;
-  const EXAMPLE_RESPONSE_PART2: usize = 195;
+    Ok(solve_puzzle(simulator, puzzle, true, None))
+  });
 
   #[test]
-  fn it_solves_example_input_part1() -> Result<()> {
-    let simulator = DumboOctopusSimulator::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(simulator, Puzzle::Part1, true);
+  fn it_renders_simulator() -> Result<()> {
+    let mut simulator =
+      DumboOctopusSimulator::from_reader(aoc_test::example!("example").input.as_bytes())?;
 
-    assert_eq!(EXAMPLE_RESPONSE_PART1, result);
-
-    Ok(())
-  }
-
-  #[test]
-  fn it_solves_example_input_part2() -> Result<()> {
-    let simulator = DumboOctopusSimulator::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(simulator, Puzzle::Part2, true);
+    simulator.simulate(2, false);
 
-    assert_eq!(EXAMPLE_RESPONSE_PART2, result);
+    insta::assert_snapshot!(aoc_test::render(&simulator));
 
     Ok(())
   }
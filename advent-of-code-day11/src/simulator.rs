@@ -4,10 +4,9 @@ use std::io::{BufRead, BufReader, Read};
 
 use bounded_integer::BoundedU32;
 use colored::Colorize;
-use eyre::eyre;
 use itertools::Itertools;
 
-use aoc_core::{Error, Result};
+use aoc_core::{InputError, ParseError, ValidationError};
 
 type Energy = BoundedU32<0, 9>;
 
@@ -39,18 +38,15 @@ impl fmt::Display for DumboOctopus {
 }
 
 impl TryFrom<char> for DumboOctopus {
-  type Error = Error;
+  type Error = ParseError;
 
-  fn try_from(value: char) -> Result<Self> {
+  fn try_from(value: char) -> Result<Self, Self::Error> {
     value
       .to_digit(10)
       .and_then(BoundedU32::new)
-      .ok_or_else(|| {
-        eyre!(
-          "dumbo octopus energy should be included between {} and {}",
-          Energy::MIN_VALUE,
-          Energy::MAX_VALUE
-        )
+      .ok_or(ParseError::InvalidChar {
+        kind: "dumbo octopus energy",
+        value,
       })
       .map(Self::Charging)
   }
@@ -61,24 +57,34 @@ pub struct DumboOctopusSimulator(Vec<DumboOctopus>);
 impl DumboOctopusSimulator {
   const GRID_SIZE: usize = 10;
 
-  pub fn from_reader(reader: impl Read) -> Result<Self> {
+  pub fn from_reader(reader: impl Read) -> Result<Self, InputError> {
     let reader = BufReader::new(reader);
 
     let grid = reader
       .lines()
-      .map(|line| {
-        line.map(|s| {
-          s.trim()
-            .chars()
-            .map(DumboOctopus::try_from)
-            .collect::<Result<Vec<_>>>()
-        })
+      .enumerate()
+      .map(|(y, line)| {
+        line?
+          .trim()
+          .chars()
+          .map(DumboOctopus::try_from)
+          .collect::<Result<Vec<_>, _>>()
+          .map_err(|source| InputError::Line {
+            line: y + 1,
+            source,
+          })
       })
-      .flatten()
-      .collect::<Result<Vec<Vec<_>>>>()?;
+      .collect::<Result<Vec<Vec<_>>, _>>()?;
 
     if grid.len() != Self::GRID_SIZE || grid.iter().any(|row| row.len() != Self::GRID_SIZE) {
-      Err(eyre!("expected {0} by {0} grid", Self::GRID_SIZE))
+      Err(
+        ValidationError::GridSize {
+          kind: "dumbo octopus",
+          width: Self::GRID_SIZE,
+          height: Self::GRID_SIZE,
+        }
+        .into(),
+      )
     } else {
       // turn 2-dimension array into 1-dimension
       let grid = grid.into_iter().flatten().collect();
@@ -7,10 +7,9 @@ use std::rc::{Rc, Weak};
 
 use colored::Colorize;
 use derivative::Derivative;
-use eyre::eyre;
 use itertools::Itertools;
 
-use aoc_core::Result;
+use aoc_core::{InputError, ParseError, ValidationError};
 
 use crate::string::StringExt;
 
@@ -76,26 +75,31 @@ impl Graph {
   const START_NODE: &'static str = "start";
   const END_NODE: &'static str = "end";
 
-  pub fn from_reader(reader: impl Read) -> Result<Self> {
+  pub fn from_reader(reader: impl Read) -> Result<Self, InputError> {
     let reader = BufReader::new(reader);
 
     let edges = reader
       .lines()
       .map(|line| {
-        line.map(|s| {
-          s.trim()
-            .split_once('-')
-            .map(|(start_id, end_id)| {
-              (
-                Node::new(start_id.to_string()),
-                Node::new(end_id.to_string()),
-              )
-            })
-            .ok_or_else(|| eyre!("malformed '{}' edge", s))
-        })
+        let s = line?;
+
+        s.trim()
+          .split_once('-')
+          .map(|(start_id, end_id)| {
+            (
+              Node::new(start_id.to_string()),
+              Node::new(end_id.to_string()),
+            )
+          })
+          .ok_or_else(|| {
+            ParseError::Malformed {
+              kind: "edge",
+              value: s.clone(),
+            }
+            .into()
+          })
       })
-      .flatten()
-      .collect::<Result<Vec<_>>>()?;
+      .collect::<Result<Vec<_>, InputError>>()?;
 
     let mut nodes = HashMap::new();
 
@@ -107,8 +111,10 @@ impl Graph {
       end_node.borrow_mut().edges.push(Rc::downgrade(&start_node));
     }
 
-    if nodes.get(Self::START_NODE).is_none() || nodes.get(Self::END_NODE).is_none() {
-      Err(eyre!("missing start and/or end nodes"))
+    if !nodes.contains_key(Self::START_NODE) {
+      Err(ValidationError::Missing { kind: "start node" }.into())
+    } else if !nodes.contains_key(Self::END_NODE) {
+      Err(ValidationError::Missing { kind: "end node" }.into())
     } else {
       Ok(Self { nodes })
     }
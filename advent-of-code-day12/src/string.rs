@@ -1,9 +1,5 @@
 pub trait StringExt {
   fn is_uppercase(&self) -> bool;
-
-  fn is_lowercase(&self) -> bool {
-    !self.is_uppercase()
-  }
 }
 
 impl StringExt for str {
@@ -3,10 +3,9 @@ use std::fmt;
 use std::io::{BufReader, Read};
 
 use colored::Colorize;
-use eyre::eyre;
 use parse_display::FromStr;
 
-use aoc_core::{Error, Result};
+use aoc_core::{InputError, ParseError};
 
 #[derive(Debug, PartialEq, Eq, Hash, FromStr, Copy, Clone)]
 #[display("{x},{y}")]
@@ -55,7 +54,7 @@ impl fmt::Display for TransparentPaper {
 }
 
 impl TransparentPaper {
-  pub fn from_reader(reader: impl Read) -> Result<Self> {
+  pub fn from_reader(reader: impl Read) -> Result<Self, InputError> {
     let mut reader = BufReader::new(reader);
     let mut buffer = String::new();
 
@@ -64,12 +63,19 @@ impl TransparentPaper {
     let (dots, instructions) = buffer
       .split_once("\n\n")
       .map(|(dots, instructions)| {
+        // instructions start right after the blank line separating both sections
+        let instructions_offset = dots.lines().count() + 2;
+
         (
-          parse_as::<Point>(dots).collect::<Result<HashSet<_>>>(),
-          parse_as::<Instruction>(instructions).collect::<Result<VecDeque<_>>>(),
+          parse_as::<Point>(dots, 1).collect::<Result<HashSet<_>, _>>(),
+          parse_as::<Instruction>(instructions, instructions_offset)
+            .collect::<Result<VecDeque<_>, _>>(),
         )
       })
-      .ok_or_else(|| eyre!("invalid transparent paper"))?;
+      .ok_or(ParseError::MissingSeparator {
+        kind: "transparent paper",
+        separator: "\n\n",
+      })?;
 
     let (dots, instructions) = (dots?, instructions?);
 
@@ -129,14 +135,15 @@ impl TransparentPaper {
   }
 }
 
-fn parse_as<T>(buffer: &str) -> impl Iterator<Item = Result<T>> + '_
+fn parse_as<T>(buffer: &str, offset: usize) -> impl Iterator<Item = Result<T, InputError>> + '_
 where
   T: std::str::FromStr,
-  T::Err: Into<Error>,
+  T::Err: Into<ParseError>,
 {
-  buffer.trim().split('\n').map(|s| {
-    s.trim()
-      .parse::<T>()
-      .map_err(|e| eyre!("{}: {}", e.into(), s))
+  buffer.trim().split('\n').enumerate().map(move |(i, s)| {
+    s.trim().parse::<T>().map_err(|e| InputError::Line {
+      line: offset + i,
+      source: e.into(),
+    })
   })
 }
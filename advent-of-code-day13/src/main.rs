@@ -1,19 +1,15 @@
 mod instruction;
 
-use std::fs::File;
-use std::path::PathBuf;
 use structopt::StructOpt;
 
-use aoc_core::{Puzzle, Result};
+use aoc_core::{Puzzle, Result, Runner, RunnerOpt};
 
 use instruction::TransparentPaper;
 
 #[derive(StructOpt)]
 struct Opt {
-  #[structopt(short, long)]
-  filename: PathBuf,
-  #[structopt(short, long)]
-  puzzle: Puzzle,
+  #[structopt(flatten)]
+  runner: RunnerOpt,
 }
 
 fn solve_puzzle(mut paper: TransparentPaper, puzzle: Puzzle) -> usize {
@@ -38,48 +34,32 @@ fn main() -> Result<()> {
 
   let opt = Opt::from_args();
 
-  let paper = TransparentPaper::from_reader(File::open(opt.filename)?)?;
-  let result = solve_puzzle(paper, opt.puzzle);
+  Runner::new("day13", opt.runner)?.run(move |input, puzzle| {
+    let paper = TransparentPaper::from_reader(&input[..])?;
 
-  println!("{}", result);
-
-  Ok(())
+    Ok(solve_puzzle(paper, puzzle))
+  })
 }
 
 #[cfg(test)]
 mod test {
   use super::*;
 
-  const EXAMPLE_DATA: &str = r"6,10
-0,14
-9,10
-0,3
-10,4
-4,11
-6,0
-6,12
-4,1
-0,13
-10,12
-3,4
-3,0
-8,4
-1,10
-2,14
-8,10
-9,0
-
-fold along y=7
-fold along x=5";
-
-  const EXAMPLE_RESPONSE_PART1: usize = 17;
+  aoc_test::example_tests!(|input: &str, puzzle| {
+    let paper = TransparentPaper::from_reader(input.as_bytes())?;
+
+    Ok(solve_puzzle(paper, puzzle))
+  });
 
+  // part 2 prints the code to read on the folded paper and answers 0, so its example has no
+  // expected answer and the rendering is checked here instead
   #[test]
-  fn it_solves_example_input_part1() -> Result<()> {
-    let paper = TransparentPaper::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(paper, Puzzle::Part1);
+  fn it_renders_folded_paper() -> Result<()> {
+    let mut paper = TransparentPaper::from_reader(aoc_test::example!("example").input.as_bytes())?;
+
+    paper.fold();
 
-    assert_eq!(result, EXAMPLE_RESPONSE_PART1);
+    insta::assert_snapshot!(aoc_test::render(&paper));
 
     Ok(())
   }
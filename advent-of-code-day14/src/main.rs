@@ -1,26 +1,22 @@
 mod polymer;
 
-use std::fs::File;
-use std::path::PathBuf;
 use structopt::StructOpt;
 
-use aoc_core::{Puzzle, Result};
+use aoc_core::{Puzzle, Result, Runner, RunnerOpt};
 
 use polymer::Polymer;
 
 #[derive(StructOpt)]
 struct Opt {
-  #[structopt(short, long)]
-  filename: PathBuf,
-  #[structopt(short, long)]
-  puzzle: Puzzle,
+  #[structopt(flatten)]
+  runner: RunnerOpt,
 }
 
-fn solve_puzzle(mut polymer: Polymer, puzzle: Puzzle) -> u128 {
-  let cycle = match puzzle {
+fn solve_puzzle(mut polymer: Polymer, puzzle: Puzzle, cycles: Option<usize>) -> u128 {
+  let cycle = cycles.unwrap_or(match puzzle {
     Puzzle::Part1 => 10,
     Puzzle::Part2 => 40,
-  };
+  });
 
   polymer.polymerize(cycle)
 }
@@ -29,58 +25,23 @@ fn main() -> Result<()> {
   color_eyre::install()?;
 
   let opt = Opt::from_args();
+  let runner = Runner::new("day14", opt.runner)?;
+  let cycles = runner.get_parameter("cycles");
 
-  let polymer = Polymer::from_reader(File::open(opt.filename)?)?;
-  let result = solve_puzzle(polymer, opt.puzzle);
+  runner.run(move |input, puzzle| {
+    let polymer = Polymer::from_reader(&input[..])?;
 
-  println!("{}", result);
-
-  Ok(())
+    Ok(solve_puzzle(polymer, puzzle, cycles))
+  })
 }
 
 #[cfg(test)]
 mod test {
   use super::*;
 
-  const EXAMPLE_DATA: &str = r"NNCB
-
-CH -> B
-HH -> N
-CB -> H
-NH -> C
-HB -> C
-HC -> B
-HN -> C
-NN -> C
-BH -> H
-NC -> B
-NB -> B
-BN -> B
-BB -> N
-BC -> B
-CC -> N
-CN -> C";
-
-  const EXAMPLE_RESPONSE_PART1: u128 = 1588;
-  const EXAMPLE_RESPONSE_PART2: u128 = 2188189693529;
-
-  #[test]
-  fn it_solves_example_input_part1() -> Result<()> {
-    let polymer = Polymer::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(polymer, Puzzle::Part1);
-
-    assert_eq!(result, EXAMPLE_RESPONSE_PART1);
-
-    Ok(())
-  }
-
-  #[test]
-  fn it_solves_example_input_part2() -> Result<()> {
-    let polymer = Polymer::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(polymer, Puzzle::Part2);
-
-    assert_eq!(result, EXAMPLE_RESPONSE_PART2);
+  aoc_test::example_tests!(|input: &str, puzzle| {
+    let polymer = Polymer::from_reader(input.as_bytes())?;
 
-    Ok(())
-  }
+    Ok(solve_puzzle(polymer, puzzle, None))
+  });
 }
@@ -3,8 +3,7 @@ use std::hash::Hash;
 use std::io::{BufReader, Read};
 use std::ops::AddAssign;
 
-use aoc_core::Result;
-use eyre::eyre;
+use aoc_core::{InputError, ParseError};
 
 pub struct Polymer {
   template: Vec<u8>,
@@ -13,7 +12,7 @@ pub struct Polymer {
 }
 
 impl Polymer {
-  pub fn from_reader(reader: impl Read) -> Result<Self> {
+  pub fn from_reader(reader: impl Read) -> Result<Self, InputError> {
     let mut reader = BufReader::new(reader);
     let mut buffer = String::new();
 
@@ -34,13 +33,19 @@ impl Polymer {
               {
                 Some((&[a, b], &[c])) if a == b => Ok(([a, b], (c, vec![HashMap::from([(a, 2)])]))),
                 Some((&[a, b], &[c])) => Ok(([a, b], (c, vec![HashMap::from([(a, 1), (b, 1)])]))),
-                _ => Err(eyre!("invalid '{}' pair insertion", s)),
+                _ => Err(ParseError::Malformed {
+                  kind: "pair insertion",
+                  value: s.to_string(),
+                }),
               }
             })
-            .collect::<Result<HashMap<_, _>>>(),
+            .collect::<Result<HashMap<_, _>, _>>(),
         )
       })
-      .ok_or_else(|| eyre!("invalid polymer formula"))?;
+      .ok_or(ParseError::MissingSeparator {
+        kind: "polymer formula",
+        separator: "\n\n",
+      })?;
 
     let pair_insertions = pair_insertions?;
 
@@ -1,11 +1,9 @@
 use std::collections::VecDeque;
 use std::convert::TryFrom;
-use std::io::{BufRead, BufReader, Read};
 
 use bounded_integer::BoundedU32;
-use eyre::eyre;
 
-use aoc_core::{Error, Result};
+use aoc_core::{Input, InputError, ParseError, ValidationError};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Cell {
@@ -21,12 +19,15 @@ impl Cell {
 }
 
 impl TryFrom<char> for Cell {
-  type Error = Error;
+  type Error = ParseError;
 
-  fn try_from(c: char) -> Result<Self> {
+  fn try_from(c: char) -> Result<Self, Self::Error> {
     c.to_digit(10)
       .and_then(BoundedU32::new)
-      .ok_or_else(|| eyre!("invalid '{}' risk level", c))
+      .ok_or(ParseError::InvalidChar {
+        kind: "risk level",
+        value: c,
+      })
       .map(|risk| Self { x: 0, y: 0, risk })
   }
 }
@@ -38,37 +39,33 @@ pub struct Finder {
 }
 
 impl Finder {
-  pub fn from_reader(reader: impl Read) -> Result<Self> {
-    let reader = BufReader::new(reader);
-
-    let cells = reader
-      .lines()
-      .enumerate()
-      .map(|(y, line)| {
-        line.map(|s| {
-          s.trim()
-            .chars()
-            .enumerate()
-            .map(|(x, c)| {
-              Ok(Cell {
-                x,
-                y,
-                ..Cell::try_from(c)?
-              })
-            })
-            .collect::<Result<Vec<_>>>()
-        })
-      })
-      .flatten()
-      .collect::<Result<Vec<Vec<_>>>>()?;
+  pub fn from_input(input: &Input) -> Result<Self, InputError> {
+    let mut cells = vec![];
+    let mut width = None;
+    let mut height = 0;
+
+    for (y, line) in input.lines().enumerate() {
+      let line = aoc_core::trim_bytes(line);
+
+      if *width.get_or_insert(line.len()) != line.len() {
+        return Err(ValidationError::UnevenRows { kind: "map" }.into());
+      }
+
+      for (x, byte) in line.iter().enumerate() {
+        cells.push(Cell {
+          x,
+          y,
+          ..Cell::try_from(*byte as char).map_err(|source| InputError::Line {
+            line: y + 1,
+            source,
+          })?
+        });
+      }
 
-    if cells.windows(2).any(|rows| rows[0].len() != rows[1].len()) {
-      return Err(eyre!("invalid map: row length should be all the same"));
+      height += 1;
     }
 
-    let width = cells[0].len();
-    let height = cells.len();
-    let cells = cells.into_iter().flatten().collect::<Vec<_>>();
+    let width = width.ok_or(ValidationError::Empty { kind: "map" })?;
 
     Ok(Self {
       width,
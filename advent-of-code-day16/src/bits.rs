@@ -1,12 +1,11 @@
 use std::io::{BufReader, Read};
 
 use derive_more::Deref;
-use eyre::eyre;
 
-use aoc_core::Result;
+use aoc_core::{InputError, ParseError, ValidationError};
 
 trait Decode {
-  fn decode(bits: &[u8]) -> Result<(usize, Self)>
+  fn decode(bits: &[u8]) -> Result<(usize, Self), ParseError>
   where
     Self: Sized;
 }
@@ -18,11 +17,14 @@ struct PacketHeader {
 }
 
 impl Decode for PacketHeader {
-  fn decode(bits: &[u8]) -> Result<(usize, Self)> {
+  fn decode(bits: &[u8]) -> Result<(usize, Self), ParseError> {
     const HEADER_LENGTH: usize = 6;
 
     if bits.len() < HEADER_LENGTH {
-      Err(eyre!("invalid header packet: {:?}", bits))
+      Err(ParseError::Malformed {
+        kind: "header packet",
+        value: format!("{:?}", bits),
+      })
     } else {
       Ok((
         HEADER_LENGTH,
@@ -39,7 +41,7 @@ impl Decode for PacketHeader {
 struct LiteralValuePacket(usize);
 
 impl Decode for LiteralValuePacket {
-  fn decode(bits: &[u8]) -> Result<(usize, Self)>
+  fn decode(bits: &[u8]) -> Result<(usize, Self), ParseError>
   where
     Self: Sized,
   {
@@ -62,7 +64,10 @@ impl Decode for LiteralValuePacket {
       .collect::<Vec<_>>();
 
     if state {
-      Err(eyre!("invalid literal value packet: {:?}", bits))
+      Err(ParseError::Malformed {
+        kind: "literal value packet",
+        value: format!("{:?}", bits),
+      })
     } else {
       Ok((bits.len() + bits.len() / 4, Self(bits_to_usize(&bits))))
     }
@@ -78,7 +83,7 @@ struct OperationPacket {
 }
 
 impl Decode for OperationPacket {
-  fn decode(bits: &[u8]) -> Result<(usize, Self)>
+  fn decode(bits: &[u8]) -> Result<(usize, Self), ParseError>
   where
     Self: Sized,
   {
@@ -103,7 +108,10 @@ impl Decode for OperationPacket {
         }
 
         if offset > value {
-          Err(eyre!("invalid operation packet: {:?}", bits))
+          Err(ParseError::Malformed {
+            kind: "operation packet",
+            value: format!("{:?}", bits),
+          })
         } else {
           Ok((
             16 + value,
@@ -137,7 +145,10 @@ impl Decode for OperationPacket {
           },
         ))
       }
-      _ => Err(eyre!("invalid '{}' labeled bit", label)),
+      _ => Err(ParseError::Malformed {
+        kind: "labeled bit",
+        value: label.to_string(),
+      }),
     }
   }
 }
@@ -170,7 +181,7 @@ impl Packet {
     }
   }
 
-  pub fn evaluate(&self) -> Result<usize> {
+  pub fn evaluate(&self) -> Result<usize, ValidationError> {
     match &self.content {
       PacketContent::LiteralValue(content) => Ok(content.0),
       PacketContent::Operation(content) => {
@@ -180,7 +191,7 @@ impl Packet {
             .packets
             .iter()
             .map(Packet::evaluate)
-            .collect::<Result<Vec<_>>>()?[..],
+            .collect::<Result<Vec<_>, _>>()?[..],
         ) {
           (0, values) => Ok(values.iter().sum::<usize>()),
           (1, values) => Ok(values.iter().product::<usize>()),
@@ -189,7 +200,10 @@ impl Packet {
           (5, &[a, b]) => Ok((a > b).then(|| 1).unwrap_or(0)),
           (6, &[a, b]) => Ok((a < b).then(|| 1).unwrap_or(0)),
           (7, &[a, b]) => Ok((a == b).then(|| 1).unwrap_or(0)),
-          _ => Err(eyre!("invalid operation")),
+          (r#type, _) => Err(ValidationError::Unsupported {
+            kind: "operation",
+            value: r#type.to_string(),
+          }),
         }
       }
     }
@@ -200,7 +214,7 @@ impl Packet {
 pub struct PacketDecoder(Packet);
 
 impl PacketDecoder {
-  pub fn from_reader(reader: impl Read) -> Result<Self> {
+  pub fn from_reader(reader: impl Read) -> Result<Self, InputError> {
     let mut reader = BufReader::new(reader);
     let mut buffer = String::new();
 
@@ -210,7 +224,7 @@ impl PacketDecoder {
       .trim()
       .chars()
       .map(hex_to_bits)
-      .collect::<Result<Vec<_>>>()?
+      .collect::<Result<Vec<_>, _>>()?
       .into_iter()
       .flatten()
       .collect::<Vec<_>>();
@@ -219,7 +233,7 @@ impl PacketDecoder {
   }
 }
 
-fn decode_packet(bits: &[u8]) -> Result<Packet> {
+fn decode_packet(bits: &[u8]) -> Result<Packet, ParseError> {
   let (header_size, header) = PacketHeader::decode(bits)?;
 
   let (content_size, content) = match header.r#type {
@@ -238,7 +252,7 @@ fn decode_packet(bits: &[u8]) -> Result<Packet> {
   })
 }
 
-fn hex_to_bits(c: char) -> Result<[u8; 4]> {
+fn hex_to_bits(c: char) -> Result<[u8; 4], ParseError> {
   match c {
     '0' => Ok([0, 0, 0, 0]),
     '1' => Ok([0, 0, 0, 1]),
@@ -256,7 +270,10 @@ fn hex_to_bits(c: char) -> Result<[u8; 4]> {
     'D' => Ok([1, 1, 0, 1]),
     'E' => Ok([1, 1, 1, 0]),
     'F' => Ok([1, 1, 1, 1]),
-    _ => Err(eyre!("invalid '{}' hexadecimal", c)),
+    _ => Err(ParseError::InvalidChar {
+      kind: "hexadecimal",
+      value: c,
+    }),
   }
 }
 
@@ -1,25 +1,21 @@
 mod prob;
 
-use std::fs::File;
-use std::path::PathBuf;
 use structopt::StructOpt;
 
-use aoc_core::{Puzzle, Result};
+use aoc_core::{Puzzle, Result, Runner, RunnerOpt};
 
 use prob::ProbLauncher;
 
 #[derive(StructOpt)]
 struct Opt {
-  #[structopt(short, long)]
-  filename: PathBuf,
-  #[structopt(short, long)]
-  puzzle: Puzzle,
+  #[structopt(flatten)]
+  runner: RunnerOpt,
 }
 
-fn solve_puzzle(launcher: ProbLauncher, puzzle: Puzzle) -> usize {
+fn solve_puzzle(launcher: ProbLauncher, puzzle: Puzzle, threads: usize) -> usize {
   match puzzle {
     Puzzle::Part1 => launcher.compute_coolest_launch(),
-    Puzzle::Part2 => launcher.calculate_successful_launches(),
+    Puzzle::Part2 => launcher.calculate_successful_launches(threads),
   }
 }
 
@@ -28,37 +24,35 @@ fn main() -> Result<()> {
 
   let opt = Opt::from_args();
 
-  let launcher = ProbLauncher::from_reader(File::open(opt.filename)?)?;
-  let result = solve_puzzle(launcher, opt.puzzle);
+  let runner = Runner::new("day17", opt.runner)?;
+  let threads = runner.get_threads();
 
-  println!("{}", result);
+  runner.run(move |input, puzzle| {
+    let launcher = ProbLauncher::from_reader(&input[..])?;
 
-  Ok(())
+    Ok(solve_puzzle(launcher, puzzle, threads))
+  })
 }
 
 #[cfg(test)]
 mod test {
   use super::*;
 
-  const EXAMPLE_DATA: &str = "target area: x=20..30, y=-10..-5";
+  aoc_test::example_tests!(|input: &str, puzzle| {
+    let launcher = ProbLauncher::from_reader(input.as_bytes())?;
 
-  const EXAMPLE_RESPONSE_PART1: usize = 45;
-  const EXAMPLE_RESPONSE_PART2: usize = 112;
+    Ok(solve_puzzle(launcher, puzzle, 1))
+  });
 
   #[test]
-  fn it_solves_example_input_part1() -> Result<()> {
-    let launcher = ProbLauncher::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(launcher, Puzzle::Part1);
-    assert_eq!(result, EXAMPLE_RESPONSE_PART1);
+  fn it_counts_launches_across_threads() -> Result<()> {
+    let input = aoc_test::example!("example").input;
 
-    Ok(())
-  }
+    for threads in 1..=4 {
+      let launcher = ProbLauncher::from_reader(input.as_bytes())?;
 
-  #[test]
-  fn it_solves_example_input_part2() -> Result<()> {
-    let launcher = ProbLauncher::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(launcher, Puzzle::Part2);
-    assert_eq!(result, EXAMPLE_RESPONSE_PART2);
+      assert_eq!(launcher.calculate_successful_launches(threads), 112);
+    }
 
     Ok(())
   }
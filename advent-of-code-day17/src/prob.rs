@@ -2,37 +2,39 @@ use std::cmp::Ordering;
 use std::io::{BufRead, BufReader, Read};
 use std::ops::RangeInclusive;
 use std::str::FromStr;
+use std::sync::Arc;
+use std::thread;
 
-use eyre::eyre;
 use itertools::Itertools;
 use regex::Regex;
 
-use aoc_core::{Error, Result};
+use aoc_core::{InputError, ParseError};
 
+#[derive(Clone)]
 struct Area {
   x: RangeInclusive<isize>,
   y: RangeInclusive<isize>,
 }
 
 impl FromStr for Area {
-  type Err = Error;
+  type Err = ParseError;
 
-  fn from_str(s: &str) -> Result<Self> {
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
     let re = Regex::new(r"^target area: x=(-?\d+)..(-?\d+), y=(-?\d+)..(-?\d+)$").unwrap();
 
-    let values = &re
+    let values = re
       .captures_iter(s)
       .map(|capture| {
         (1..=4)
           .into_iter()
-          .map(|i| {
-            capture[i]
-              .parse::<isize>()
-              .map_err(|e| eyre!("malformed area: {}", e))
-          })
-          .collect::<Result<Vec<_>>>()
+          .map(|i| capture[i].parse::<isize>())
+          .collect::<Result<Vec<_>, _>>()
       })
-      .collect::<Result<Vec<Vec<_>>>>()?[0];
+      .next()
+      .ok_or_else(|| ParseError::Malformed {
+        kind: "area",
+        value: s.to_string(),
+      })??;
 
     Ok(Self {
       x: values[0]..=values[1],
@@ -46,13 +48,13 @@ pub struct ProbLauncher {
 }
 
 impl ProbLauncher {
-  pub fn from_reader(reader: impl Read) -> Result<Self> {
+  pub fn from_reader(reader: impl Read) -> Result<Self, InputError> {
     let mut reader = BufReader::new(reader);
     let mut buffer = String::new();
 
     reader.read_line(&mut buffer)?;
 
-    let target_area = buffer.trim().parse()?;
+    let target_area = buffer.trim().parse::<Area>()?;
 
     Ok(Self { target_area })
   }
@@ -73,12 +75,30 @@ impl ProbLauncher {
     (self.target_area.y.start() * -1) - 1
   }
 
-  pub fn calculate_successful_launches(&self) -> usize {
-    (0..=*self.target_area.x.end())
+  // Spreads the horizontal velocities over the threads, each one trying every `threads`-th value.
+  pub fn calculate_successful_launches(&self, threads: usize) -> usize {
+    let threads = threads.max(1);
+    let area = Arc::new(self.target_area.clone());
+    let highest_dy = self.get_highest_dy();
+
+    let handles = (0..threads)
+      .map(|offset| {
+        let area = Arc::clone(&area);
+
+        thread::spawn(move || {
+          (offset as isize..=*area.x.end())
+            .step_by(threads)
+            .cartesian_product(*area.y.start()..=highest_dy)
+            .filter(|(dx, dy)| emulate_launch(*dx, *dy, &area))
+            .count()
+        })
+      })
+      .collect::<Vec<_>>();
+
+    handles
       .into_iter()
-      .cartesian_product(*self.target_area.y.start()..=self.get_highest_dy())
-      .filter(|(dx, dy)| emulate_launch(*dx, *dy, &self.target_area))
-      .count()
+      .map(|handle| handle.join().expect("launch emulation panicked"))
+      .sum()
   }
 }
 
@@ -0,0 +1,184 @@
+use parse_display::{Display, FromStr};
+use strum::EnumString;
+use thiserror::Error;
+
+use crate::submarine::{Command, Console, Orientation, Position};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Mode {
+  // stop at the first violation
+  Strict,
+  // keep going and report every violation as a warning
+  Lenient,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display, FromStr)]
+#[display("{start}..{end}")]
+pub struct Zone {
+  pub start: isize,
+  pub end: isize,
+}
+
+impl Zone {
+  // Gives the first position of the zone met while moving from one horizontal position to the
+  // other, so that passing through it within a single command is caught too.
+  fn find_entry(&self, from: isize, to: isize) -> Option<isize> {
+    let start = from.min(to).max(self.start);
+    let end = from.max(to).min(self.end);
+
+    match (start <= end, from <= to) {
+      (false, _) => None,
+      (true, true) => Some(start),
+      (true, false) => Some(end),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum Violation {
+  #[error("rose above the {floor} surface floor to depth {depth}")]
+  Surface { floor: isize, depth: isize },
+  #[error("dived below the {max} maximum depth to depth {depth}")]
+  MaxDepth { max: isize, depth: isize },
+  #[error("steered beyond the {max} maximum aim to aim {aim}")]
+  MaxAim { max: isize, aim: isize },
+  #[error("entered the {zone} forbidden zone at {horizontal_position}")]
+  ForbiddenZone {
+    zone: Zone,
+    horizontal_position: isize,
+  },
+}
+
+#[derive(Debug, PartialEq, Eq, Error)]
+#[error("command {step} ({command}) {violation}")]
+pub struct Alarm {
+  pub step: usize,
+  pub command: String,
+  pub violation: Violation,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Constraints {
+  pub surface_floor: Option<isize>,
+  pub max_depth: Option<isize>,
+  pub max_aim: Option<isize>,
+  pub forbidden_zones: Vec<Zone>,
+}
+
+impl Constraints {
+  // Compares the state of the console after a command with the constraints, the horizontal
+  // position it started from telling which positions the command went through.
+  pub fn check(
+    &self,
+    previous_horizontal_position: isize,
+    console: &dyn Console,
+  ) -> Vec<Violation> {
+    let depth = console.get_depth();
+    let aim = console.get_aim();
+    let horizontal_position = console.get_horizontal_position();
+
+    let mut violations = vec![];
+
+    if let Some(floor) = self.surface_floor.filter(|floor| depth < *floor) {
+      violations.push(Violation::Surface { floor, depth });
+    }
+
+    if let Some(max) = self.max_depth.filter(|max| depth > *max) {
+      violations.push(Violation::MaxDepth { max, depth });
+    }
+
+    if let Some(max) = self.max_aim.filter(|max| aim.abs() > *max) {
+      violations.push(Violation::MaxAim { max, aim });
+    }
+
+    violations.extend(self.forbidden_zones.iter().filter_map(|zone| {
+      zone
+        .find_entry(previous_horizontal_position, horizontal_position)
+        .map(|horizontal_position| Violation::ForbiddenZone {
+          zone: *zone,
+          horizontal_position,
+        })
+    }));
+
+    violations
+  }
+}
+
+// Checks the constraints after each command given to the wrapped console. In strict mode the
+// first alarm halts it and any later command is ignored.
+pub struct Guard<C> {
+  console: C,
+  constraints: Constraints,
+  mode: Mode,
+  step: usize,
+  alarms: Vec<Alarm>,
+}
+
+impl<C: Console> Guard<C> {
+  pub fn new(console: C, constraints: Constraints, mode: Mode) -> Self {
+    Self {
+      console,
+      constraints,
+      mode,
+      step: 0,
+      alarms: vec![],
+    }
+  }
+
+  fn is_halted(&self) -> bool {
+    self.mode == Mode::Strict && !self.alarms.is_empty()
+  }
+
+  // Gives back the console with the warnings raised in lenient mode, or the alarm which halted it.
+  pub fn finish(mut self) -> Result<(C, Vec<Alarm>), Alarm> {
+    match self.mode {
+      Mode::Strict if !self.alarms.is_empty() => Err(self.alarms.remove(0)),
+      _ => Ok((self.console, self.alarms)),
+    }
+  }
+}
+
+impl<C: Console> Console for Guard<C> {
+  fn interpret(&mut self, command: &Command) {
+    if self.is_halted() {
+      return;
+    }
+
+    let previous_horizontal_position = self.console.get_horizontal_position();
+
+    self.step += 1;
+    self.console.interpret(command);
+
+    for violation in self
+      .constraints
+      .check(previous_horizontal_position, &self.console)
+    {
+      self.alarms.push(Alarm {
+        step: self.step,
+        command: command.to_string(),
+        violation,
+      });
+    }
+  }
+
+  fn get_depth(&self) -> isize {
+    self.console.get_depth()
+  }
+
+  fn get_horizontal_position(&self) -> isize {
+    self.console.get_horizontal_position()
+  }
+
+  fn get_aim(&self) -> isize {
+    self.console.get_aim()
+  }
+
+  fn get_position(&self) -> Position {
+    self.console.get_position()
+  }
+
+  fn get_orientation(&self) -> Orientation {
+    self.console.get_orientation()
+  }
+}
@@ -1,31 +1,80 @@
+mod constraint;
+mod planner;
+mod recorder;
+mod script;
 mod submarine;
 
-use std::fs::File;
+use std::fs;
 use std::path::PathBuf;
 
 use structopt::StructOpt;
 
-use aoc_core::{Puzzle, Result};
-use submarine::{Command, Console, FixedSubmarine, Submarine};
+use aoc_core::{Puzzle, Result, Runner, RunnerOpt};
+use constraint::{Alarm, Constraints, Guard, Mode, Zone};
+use planner::{Objective, Target};
+use recorder::{Recorder, TrajectoryFormat};
+use script::Script;
+use submarine::{Console, Model};
 
 #[derive(StructOpt)]
 struct Opt {
+  #[structopt(flatten)]
+  runner: RunnerOpt,
   #[structopt(short, long)]
-  filename: PathBuf,
-  #[structopt(short, long)]
-  puzzle: Puzzle,
+  debug: bool,
+  /// Write the state reached after each command to this file
+  #[structopt(long, parse(from_os_str))]
+  trajectory: Option<PathBuf>,
+  #[structopt(long, default_value = "csv")]
+  trajectory_format: TrajectoryFormat,
+  /// Report the first command taking the submarine deeper than this
+  #[structopt(long)]
+  depth_alarm: Option<isize>,
+  /// Planar, aim or spatial, defaults to the model of the puzzle part
+  #[structopt(long)]
+  model: Option<Model>,
+  /// Plan the commands reaching `<horizontal position>,<depth>` instead of running the input
+  #[structopt(long)]
+  target: Option<Target>,
+  /// Minimise the command count or their total magnitude
+  #[structopt(long, default_value = "count")]
+  objective: Objective,
+  /// Lowest depth allowed, e.g. 0 for the surface
+  #[structopt(long)]
+  surface_floor: Option<isize>,
+  #[structopt(long)]
+  max_depth: Option<isize>,
+  #[structopt(long)]
+  max_aim: Option<isize>,
+  /// Horizontal positions the submarine must stay out of, as `<start>..<end>` (repeatable)
+  #[structopt(long, number_of_values = 1)]
+  forbidden_zone: Vec<Zone>,
+  /// Stop at the first constraint violation (strict) or only warn about them (lenient)
+  #[structopt(long, default_value = "strict")]
+  constraint_mode: Mode,
 }
 
-fn solve_puzzle(commands: Vec<Command>, puzzle: Puzzle) -> isize {
-  let mut submarine: Box<dyn Console> = match puzzle {
-    Puzzle::Part1 => Box::new(Submarine::default()),
-    Puzzle::Part2 => Box::new(FixedSubmarine::default()),
-  };
+fn get_model(puzzle: Puzzle) -> Model {
+  match puzzle {
+    Puzzle::Part1 => Model::Planar,
+    Puzzle::Part2 => Model::Aim,
+  }
+}
+
+fn pilot(
+  script: &Script,
+  model: Model,
+  constraints: Constraints,
+  mode: Mode,
+) -> std::result::Result<(Recorder, Vec<Alarm>), Alarm> {
+  let mut guard = Guard::new(Recorder::new(model.create_console()), constraints, mode);
 
-  commands
-    .iter()
-    .for_each(|command| submarine.interpret(command));
+  script.run(&mut guard);
 
+  guard.finish()
+}
+
+fn solve_puzzle(submarine: &dyn Console) -> isize {
   submarine.get_depth() * submarine.get_horizontal_position()
 }
 
@@ -33,45 +82,325 @@ fn main() -> Result<()> {
   color_eyre::install()?;
 
   let opt = Opt::from_args();
-  let commands = aoc_core::read_lines(File::open(opt.filename)?)?;
-  let result = solve_puzzle(commands, opt.puzzle);
+  let debug = opt.debug;
+  let trajectory = opt.trajectory;
+  let trajectory_format = opt.trajectory_format;
+  let depth_alarm = opt.depth_alarm;
+  let model = opt.model;
+  let objective = opt.objective;
+  let constraints = Constraints {
+    surface_floor: opt.surface_floor,
+    max_depth: opt.max_depth,
+    max_aim: opt.max_aim,
+    forbidden_zones: opt.forbidden_zone,
+  };
+  let constraint_mode = opt.constraint_mode;
+  let runner = Runner::new("day2", opt.runner)?;
 
-  println!("{}", result);
+  if let Some(target) = opt.target {
+    return runner.run_without_input(move |puzzle| {
+      let model = model.unwrap_or_else(|| get_model(puzzle));
 
-  Ok(())
+      Ok(planner::plan(model, target, objective)?)
+    });
+  }
+
+  runner.run(move |input, puzzle| {
+    let script = Script::from_reader(&input[..])?;
+    let model = model.unwrap_or_else(|| get_model(puzzle));
+    let (recorder, warnings) = pilot(&script, model, constraints, constraint_mode)?;
+
+    for warning in warnings.iter() {
+      eprintln!("warning: {}", warning);
+    }
+
+    if debug {
+      let position = recorder.get_position();
+
+      println!(
+        "position: x {:.2}, y {:.2}, depth {:.2}",
+        position.x, position.y, position.depth
+      );
+      println!(
+        "max depth: {}, total distance: {:.2}",
+        recorder.get_max_depth().unwrap_or_default(),
+        recorder.get_total_distance()
+      );
+    }
+
+    if let Some(depth) = depth_alarm {
+      match recorder.find_depth_exceeded(depth) {
+        Some(state) => println!(
+          "depth {} first exceeded at step {} ({})",
+          depth, state.step, state.command
+        ),
+        None => println!("depth {} never exceeded", depth),
+      }
+    }
+
+    if let Some(path) = trajectory {
+      fs::write(path, recorder.export(trajectory_format)?)?;
+    }
+
+    Ok(solve_puzzle(&recorder))
+  })
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
-  const EXAMPLE_DATA: &str = r"forward 5
-down 5
-forward 8
-up 3
-down 8
-forward 2
-";
+  use aoc_core::{InputError, ParseError};
+
+  use constraint::Violation;
 
-  const EXAMPLE_RESPONSE_PART1: isize = 150;
-  const EXAMPLE_RESPONSE_PART2: isize = 900;
+  aoc_test::example_tests!(|input: &str, puzzle| {
+    let script = Script::from_reader(input.as_bytes())?;
+
+    let (recorder, _) = pilot(
+      &script,
+      get_model(puzzle),
+      Constraints::default(),
+      Mode::Strict,
+    )?;
+
+    Ok(solve_puzzle(&recorder))
+  });
 
   #[test]
-  fn it_solves_example_input_part1() -> Result<()> {
-    let commands = aoc_core::read_lines(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(commands, Puzzle::Part1);
+  fn it_records_trajectory() -> Result<()> {
+    let script = Script::from_reader(aoc_test::example!("example").input.as_bytes())?;
+    let (recorder, _) = pilot(&script, Model::Aim, Constraints::default(), Mode::Strict)?;
 
-    assert_eq!(EXAMPLE_RESPONSE_PART1, result);
+    assert_eq!(recorder.get_max_depth(), Some(60));
+    assert_eq!(
+      recorder.find_depth_exceeded(10).map(|state| state.step),
+      Some(3)
+    );
+    assert_eq!(recorder.find_depth_exceeded(60), None);
+    assert_eq!(recorder.get_total_distance().round(), 66.0);
+
+    insta::assert_snapshot!(recorder.export(TrajectoryFormat::Csv)?);
 
     Ok(())
   }
 
   #[test]
-  fn it_solves_examples_input_part2() -> Result<()> {
-    let commands = aoc_core::read_lines(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(commands, Puzzle::Part2);
+  fn it_reports_script_error_position() {
+    let parse = |script: &str| match Script::from_reader(script.as_bytes()) {
+      Err(InputError::Line {
+        line,
+        source: ParseError::MalformedAt { kind, column, .. },
+      }) => Some((line, kind, column)),
+      _ => None,
+    };
+
+    assert_eq!(parse("forward 5\n  down x\n"), Some((2, "value", 8)));
+    assert_eq!(parse("# comment\n\nsideways 2"), Some((3, "direction", 1)));
+    assert_eq!(parse("forward 1\n  dive\n"), Some((2, "macro call", 3)));
+    assert_eq!(parse("repeat 2 {\n  up 1\n}\n}"), Some((4, "block", 1)));
+    assert_eq!(
+      parse("up 1\n repeat 2 {\n  up 1\n"),
+      Some((2, "unclosed block", 2))
+    );
+  }
+
+  #[test]
+  fn it_steers_spatial_submarine() -> Result<()> {
+    let script =
+      "turn left 90\nforward 10\npitch down 30\nforward 10\nturn right 360\nbackward 4\n";
+    let script = Script::from_reader(script.as_bytes())?;
+    let (recorder, _) = pilot(
+      &script,
+      Model::Spatial,
+      Constraints::default(),
+      Mode::Strict,
+    )?;
+    let position = recorder.get_position();
+
+    assert!(position.x.abs() < 1e-9);
+    assert!((position.y - (10.0 + 30f64.to_radians().cos() * 6.0)).abs() < 1e-9);
+    assert!((position.depth - 3.0).abs() < 1e-9);
+
+    let trajectory =
+      serde_json::from_str::<serde_json::Value>(&recorder.export(TrajectoryFormat::Json)?)?;
+    let state = &trajectory[5];
+
+    assert_eq!(state["heading"], 90.0);
+    assert_eq!(state["pitch"], -30.0);
+    assert!((state["y"].as_f64().unwrap_or_default() - position.y).abs() < 1e-9);
+    assert!((state["depth"].as_f64().unwrap_or_default() - position.depth).abs() < 1e-9);
+
+    // the planar models ignore steering commands
+    let (recorder, _) = pilot(&script, Model::Planar, Constraints::default(), Mode::Strict)?;
+
+    assert_eq!(solve_puzzle(&recorder), 0);
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_plans_commands_reaching_target() -> Result<()> {
+    let target = Target {
+      horizontal_position: 15,
+      depth: 60,
+    };
+
+    assert_eq!(
+      planner::plan(Model::Aim, target, Objective::Count)?.commands,
+      vec!["down 4", "forward 15"]
+    );
+
+    let target = Target {
+      horizontal_position: 2,
+      depth: 100,
+    };
+
+    assert_eq!(
+      planner::plan(Model::Aim, target, Objective::Count)?.commands,
+      vec!["down 50", "forward 2"]
+    );
+    assert_eq!(
+      planner::plan(Model::Aim, target, Objective::Magnitude)?.commands,
+      vec!["backward 4", "down 15", "forward 1", "down 2", "forward 5"]
+    );
+
+    for horizontal_position in -12..=12 {
+      for depth in -12..=12 {
+        let target = Target {
+          horizontal_position,
+          depth,
+        };
+
+        for model in [Model::Planar, Model::Aim] {
+          assert!(planner::plan(model, target, Objective::Count)?.count <= 3);
+        }
+      }
+    }
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_plans_minimal_magnitude() -> Result<()> {
+    // breadth-first search over unit commands, exact as long as the cheapest plan stays within
+    // `LIMIT`, which bounds every coordinate it goes through
+    const LIMIT: isize = 24;
+    const MAX_DEPTH: isize = LIMIT * LIMIT / 4;
+
+    let index = |x: isize, depth: isize, aim: isize| {
+      (((x + LIMIT) * (2 * LIMIT + 1) + aim + LIMIT) * (2 * MAX_DEPTH + 1) + depth + MAX_DEPTH)
+        as usize
+    };
+    let mut magnitudes = vec![None; index(LIMIT, MAX_DEPTH, LIMIT) + 1];
+    let mut queue = std::collections::VecDeque::from([(0_isize, 0_isize, 0_isize, 0)]);
+
+    magnitudes[index(0, 0, 0)] = Some(0);
+
+    while let Some((x, depth, aim, magnitude)) = queue.pop_front() {
+      for (dx, daim) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+        let (x, depth, aim) = (x + dx, depth + aim * dx, aim + daim);
+
+        if magnitude == LIMIT || x.abs() > LIMIT || aim.abs() > LIMIT || depth.abs() > MAX_DEPTH {
+          continue;
+        }
+
+        if magnitudes[index(x, depth, aim)].is_none() {
+          magnitudes[index(x, depth, aim)] = Some(magnitude + 1);
+          queue.push_back((x, depth, aim, magnitude + 1));
+        }
+      }
+    }
+
+    for horizontal_position in -6..=6 {
+      for depth in -30..=30 {
+        let target = Target {
+          horizontal_position,
+          depth,
+        };
+        let minimum = (-LIMIT..=LIMIT)
+          .filter_map(|aim| magnitudes[index(horizontal_position, depth, aim)])
+          .min();
+        let plan = planner::plan(Model::Aim, target, Objective::Magnitude)?;
+
+        assert_eq!(Some(plan.magnitude), minimum, "{:?}", target);
+      }
+    }
+
+    let target = Target {
+      horizontal_position: 1,
+      depth: 101,
+    };
+
+    assert_eq!(
+      planner::plan(Model::Aim, target, Objective::Magnitude)?.magnitude,
+      28
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_raises_constraint_alarms() -> Result<()> {
+    let script = Script::from_reader(aoc_test::example!("example").input.as_bytes())?;
+    let constraints = Constraints {
+      surface_floor: Some(0),
+      max_depth: Some(30),
+      max_aim: Some(8),
+      forbidden_zones: vec![Zone { start: 14, end: 20 }],
+    };
+
+    let alarm = pilot(&script, Model::Aim, constraints.clone(), Mode::Strict).err();
+
+    assert_eq!(
+      alarm.map(|alarm| alarm.to_string()).as_deref(),
+      Some("command 3 (forward 8) dived below the 30 maximum depth to depth 40")
+    );
+
+    let (recorder, warnings) = pilot(&script, Model::Aim, constraints.clone(), Mode::Lenient)?;
+    let warnings = warnings
+      .into_iter()
+      .map(|warning| (warning.step, warning.violation))
+      .collect::<Vec<_>>();
+
+    assert_eq!(solve_puzzle(&recorder), 900);
+    assert_eq!(
+      warnings,
+      vec![
+        (3, Violation::MaxDepth { max: 30, depth: 40 }),
+        (4, Violation::MaxDepth { max: 30, depth: 40 }),
+        (5, Violation::MaxDepth { max: 30, depth: 40 }),
+        (5, Violation::MaxAim { max: 8, aim: 10 }),
+        (6, Violation::MaxDepth { max: 30, depth: 60 }),
+        (6, Violation::MaxAim { max: 8, aim: 10 }),
+        (
+          6,
+          Violation::ForbiddenZone {
+            zone: Zone { start: 14, end: 20 },
+            horizontal_position: 14
+          }
+        ),
+      ]
+    );
+
+    let (_, warnings) = pilot(&script, Model::Planar, constraints.clone(), Mode::Lenient)?;
+
+    assert_eq!(
+      warnings.last().map(|warning| &warning.violation),
+      Some(&Violation::ForbiddenZone {
+        zone: Zone { start: 14, end: 20 },
+        horizontal_position: 14
+      })
+    );
+
+    // the zone is crossed within a single command, from 10 to 25
+    let script = Script::from_reader("forward 10\nforward 15\n".as_bytes())?;
+    let alarm = pilot(&script, Model::Planar, constraints, Mode::Strict).err();
 
-    assert_eq!(EXAMPLE_RESPONSE_PART2, result);
+    assert_eq!(
+      alarm.map(|alarm| alarm.to_string()).as_deref(),
+      Some("command 2 (forward 15) entered the 14..20 forbidden zone at 14")
+    );
 
     Ok(())
   }
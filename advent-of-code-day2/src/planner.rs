@@ -0,0 +1,293 @@
+use std::fmt;
+
+use parse_display::FromStr;
+use serde::Serialize;
+use strum::EnumString;
+
+use aoc_core::ValidationError;
+
+use crate::submarine::{Command, Direction, Model};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Objective {
+  Count,
+  Magnitude,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, FromStr)]
+#[display("{horizontal_position},{depth}")]
+pub struct Target {
+  pub horizontal_position: isize,
+  pub depth: isize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Plan {
+  pub commands: Vec<String>,
+  pub count: usize,
+  pub magnitude: isize,
+}
+
+impl Plan {
+  fn new(commands: &[Command]) -> Self {
+    Self {
+      commands: commands.iter().map(Command::to_string).collect(),
+      count: commands.len(),
+      magnitude: get_magnitude(commands),
+    }
+  }
+}
+
+impl fmt::Display for Plan {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for command in self.commands.iter() {
+      writeln!(f, "{}", command)?;
+    }
+
+    write!(
+      f,
+      "{} command(s), total magnitude {}",
+      self.count, self.magnitude
+    )
+  }
+}
+
+fn get_magnitude(commands: &[Command]) -> isize {
+  commands.iter().map(|command| command.value).sum()
+}
+
+fn create_command(positive: Direction, negative: Direction, value: isize) -> Option<Command> {
+  match value {
+    0 => None,
+    value if value > 0 => Some(Command {
+      direction: positive,
+      value,
+    }),
+    value => Some(Command {
+      direction: negative,
+      value: -value,
+    }),
+  }
+}
+
+fn travel(distance: isize) -> Option<Command> {
+  create_command(Direction::Forward, Direction::Backward, distance)
+}
+
+fn dive(depth: isize) -> Option<Command> {
+  create_command(Direction::Down, Direction::Up, depth)
+}
+
+pub fn plan(model: Model, target: Target, objective: Objective) -> Result<Plan, ValidationError> {
+  let commands = match model {
+    Model::Planar => travel(target.horizontal_position)
+      .into_iter()
+      .chain(dive(target.depth))
+      .collect(),
+    Model::Aim => plan_aim(target, objective),
+    Model::Spatial => {
+      return Err(ValidationError::Unsupported {
+        kind: "planner model",
+        value: "spatial".to_string(),
+      })
+    }
+  };
+
+  if !verify(model, &commands, target) {
+    return Err(ValidationError::Unsupported {
+      kind: "target",
+      value: format!("{:?}", target),
+    });
+  }
+
+  Ok(Plan::new(&commands))
+}
+
+// With the aim model the depth only changes while moving, by `aim * distance`. Counting commands,
+// travelling `a`, setting the aim to `k` then travelling `b` reaches any target as `b` can be any
+// divisor of the depth, and trying each divisor gives the cheapest such plan. A zero `a` collapses
+// it to two commands whenever the horizontal position divides the depth.
+fn plan_aim(target: Target, objective: Objective) -> Vec<Command> {
+  let Target {
+    horizontal_position,
+    depth,
+  } = target;
+
+  if depth == 0 {
+    return travel(horizontal_position).into_iter().collect();
+  }
+
+  let divisors = (1..)
+    .take_while(|i| i * i <= depth.abs())
+    .filter(|i| depth % i == 0)
+    .flat_map(|i| [i, depth.abs() / i])
+    .flat_map(|i| [i, -i]);
+
+  let commands = divisors
+    .map(|distance| {
+      travel(horizontal_position - distance)
+        .into_iter()
+        .chain(dive(depth / distance))
+        .chain(travel(distance))
+        .collect::<Vec<_>>()
+    })
+    .min_by_key(|commands| (commands.len(), get_magnitude(commands)))
+    .unwrap_or_default();
+
+  match objective {
+    Objective::Count => commands,
+    Objective::Magnitude => plan_aim_magnitude(target, get_magnitude(&commands)),
+  }
+}
+
+// Any aim plan boils down to `forward` units travelled in total, `backward` ones, and the lowest
+// and highest aims reached on the way, `low <= 0 <= high`. Every unit moves the depth by an aim in
+// between, so reachable depths cover `[H * low - backward * spread, H * low + forward * spread]`
+// with `spread = high - low`, while the aim changes cost at least `spread + min(-low, high)`. The
+// search tries every total distance and spread cheaper than the best plan so far, picking for each
+// the `low` closest to either end.
+fn plan_aim_magnitude(target: Target, upper_bound: isize) -> Vec<Command> {
+  let Target {
+    horizontal_position,
+    depth,
+  } = target;
+
+  let mut best = None;
+  let mut best_magnitude = upper_bound + 1;
+
+  for distance in (horizontal_position.abs()..).step_by(2) {
+    if distance >= best_magnitude {
+      break;
+    }
+
+    let forward = (distance + horizontal_position) / 2;
+    let backward = (distance - horizontal_position) / 2;
+
+    for spread in 0..best_magnitude - distance {
+      let (min_low, max_low) = get_low_range(target, forward, backward, spread);
+
+      for low in [max_low, min_low] {
+        if low < min_low || low > max_low {
+          continue;
+        }
+
+        let magnitude = distance + spread + (-low).min(low + spread);
+
+        if magnitude < best_magnitude {
+          best_magnitude = magnitude;
+          best = Some((forward, backward, low, low + spread));
+        }
+      }
+    }
+  }
+
+  let (forward, backward, low, high) = match best {
+    Some(best) => best,
+    None => return vec![],
+  };
+
+  // splits the depth between both ways so that each one stays within its reachable range
+  let forward_depth = (depth + backward * low).clamp(forward * low, forward * high);
+  let mut segments = spread_aims(forward, forward_depth, low, high);
+
+  for (aim, distance) in spread_aims(backward, forward_depth - depth, low, high) {
+    segments.push((aim, -distance));
+  }
+
+  // moving at the same aim both ways only cancels out, then the aims are swept from the closest end
+  segments.sort_unstable();
+  segments.dedup_by(|(aim, distance), (kept_aim, kept_distance)| {
+    *aim == *kept_aim && {
+      *kept_distance += *distance;
+      true
+    }
+  });
+
+  if -low > high {
+    segments.reverse();
+  }
+
+  let mut aim = 0;
+  let mut commands = vec![];
+
+  for (segment_aim, distance) in segments.into_iter().filter(|(_, distance)| *distance != 0) {
+    commands.extend(dive(segment_aim - aim));
+    commands.extend(travel(distance));
+    aim = segment_aim;
+  }
+
+  commands
+}
+
+// Lists the lowest aims, between `-spread` and 0, from which the target depth is reachable.
+fn get_low_range(target: Target, forward: isize, backward: isize, spread: isize) -> (isize, isize) {
+  let Target {
+    horizontal_position,
+    depth,
+  } = target;
+
+  // `H * low` has to be within `[depth - forward * spread, depth + backward * spread]`
+  let (min, max) = (depth - forward * spread, depth + backward * spread);
+  let (min_low, max_low) = match horizontal_position.signum() {
+    0 if min <= 0 && 0 <= max => (-spread, 0),
+    0 => return (0, -1),
+    1 => (
+      div_ceil(min, horizontal_position),
+      div_floor(max, horizontal_position),
+    ),
+    _ => (
+      div_ceil(max, horizontal_position),
+      div_floor(min, horizontal_position),
+    ),
+  };
+
+  (min_low.max(-spread), max_low.min(0))
+}
+
+// Gives `count` units aims between `low` and `high` adding up to `total`, as `(aim, distance)`.
+fn spread_aims(count: isize, total: isize, low: isize, high: isize) -> Vec<(isize, isize)> {
+  if count == 0 {
+    return vec![];
+  }
+
+  if high == low {
+    return vec![(low, count)];
+  }
+
+  let excess = total - count * low;
+  let (at_high, remainder) = (excess / (high - low), excess % (high - low));
+  let at_remainder = (remainder > 0) as isize;
+
+  vec![
+    (low, count - at_high - at_remainder),
+    (low + remainder, at_remainder),
+    (high, at_high),
+  ]
+}
+
+fn div_floor(a: isize, b: isize) -> isize {
+  let quotient = a / b;
+
+  if a % b != 0 && (a < 0) != (b < 0) {
+    quotient - 1
+  } else {
+    quotient
+  }
+}
+
+fn div_ceil(a: isize, b: isize) -> isize {
+  -div_floor(-a, b)
+}
+
+// Replays the commands through the model itself to make sure they reach the target.
+pub fn verify(model: Model, commands: &[Command], target: Target) -> bool {
+  let mut console = model.create_console();
+
+  for command in commands.iter() {
+    console.interpret(command);
+  }
+
+  console.get_horizontal_position() == target.horizontal_position
+    && console.get_depth() == target.depth
+}
@@ -0,0 +1,133 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+use strum::EnumString;
+
+use crate::submarine::{Command, Console, Orientation, Position};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum TrajectoryFormat {
+  Csv,
+  Json,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct State {
+  pub step: usize,
+  pub command: String,
+  pub x: f64,
+  pub y: f64,
+  pub depth: f64,
+  pub aim: isize,
+  pub heading: f64,
+  pub pitch: f64,
+}
+
+// Wraps any console and keeps the state reached after each command.
+pub struct Recorder {
+  console: Box<dyn Console>,
+  trajectory: Vec<State>,
+  // straight-line distance between consecutive positions, in every dimension the console has
+  distance: f64,
+}
+
+impl Recorder {
+  pub fn new(console: Box<dyn Console>) -> Self {
+    Self {
+      console,
+      trajectory: vec![],
+      distance: 0.0,
+    }
+  }
+
+  pub fn get_max_depth(&self) -> Option<isize> {
+    self
+      .trajectory
+      .iter()
+      .map(|state| state.depth.round() as isize)
+      .max()
+  }
+
+  pub fn find_depth_exceeded(&self, depth: isize) -> Option<&State> {
+    self
+      .trajectory
+      .iter()
+      .find(|state| state.depth.round() as isize > depth)
+  }
+
+  pub fn get_total_distance(&self) -> f64 {
+    self.distance
+  }
+
+  pub fn export(&self, format: TrajectoryFormat) -> serde_json::Result<String> {
+    match format {
+      TrajectoryFormat::Csv => {
+        let mut csv = String::from("step,command,x,y,depth,aim,heading,pitch\n");
+
+        for state in self.trajectory.iter() {
+          writeln!(
+            csv,
+            "{},{},{},{},{},{},{},{}",
+            state.step,
+            state.command,
+            state.x,
+            state.y,
+            state.depth,
+            state.aim,
+            state.heading,
+            state.pitch
+          )
+          .unwrap();
+        }
+
+        Ok(csv)
+      }
+      TrajectoryFormat::Json => serde_json::to_string_pretty(&self.trajectory),
+    }
+  }
+}
+
+impl Console for Recorder {
+  fn interpret(&mut self, command: &Command) {
+    let from = self.console.get_position();
+
+    self.console.interpret(command);
+
+    let to = self.console.get_position();
+    let orientation = self.console.get_orientation();
+
+    self.distance +=
+      ((to.x - from.x).powi(2) + (to.y - from.y).powi(2) + (to.depth - from.depth).powi(2)).sqrt();
+    self.trajectory.push(State {
+      step: self.trajectory.len() + 1,
+      command: command.to_string(),
+      x: to.x,
+      y: to.y,
+      depth: to.depth,
+      aim: self.console.get_aim(),
+      heading: orientation.heading,
+      pitch: orientation.pitch,
+    });
+  }
+
+  fn get_depth(&self) -> isize {
+    self.console.get_depth()
+  }
+
+  fn get_horizontal_position(&self) -> isize {
+    self.console.get_horizontal_position()
+  }
+
+  fn get_aim(&self) -> isize {
+    self.console.get_aim()
+  }
+
+  fn get_position(&self) -> Position {
+    self.console.get_position()
+  }
+
+  fn get_orientation(&self) -> Orientation {
+    self.console.get_orientation()
+  }
+}
@@ -0,0 +1,172 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read};
+
+use aoc_core::{InputError, ParseError};
+
+use crate::submarine::{Command, Console, Direction};
+
+const COMMENT: char = '#';
+const BLOCK_START: &str = "{";
+const BLOCK_END: &str = "}";
+
+#[derive(Clone)]
+pub enum Statement {
+  Command(Command),
+  Repeat(usize, Vec<Statement>),
+}
+
+enum Block {
+  Script,
+  Repeat(usize),
+  Macro(String),
+}
+
+// A submarine script is one statement per line:
+//
+//   # comments start with a hash, blank lines are ignored
+//   macro dive {
+//     down 5
+//     forward 8
+//   }
+//   repeat 3 {
+//     dive
+//     backward 2
+//   }
+//
+// Macros are expanded where they are called, so they have to be defined beforehand.
+pub struct Script(Vec<Statement>);
+
+impl Script {
+  pub fn from_reader(reader: impl Read) -> Result<Self, InputError> {
+    let mut macros = HashMap::<String, Vec<Statement>>::new();
+    // every open block with the line and column where it was opened
+    let mut blocks = vec![(Block::Script, 0, 0, vec![])];
+
+    for (i, line) in BufReader::new(reader).lines().enumerate() {
+      let line = line?;
+      let to_error = |kind, value: &str, column| InputError::Line {
+        line: i + 1,
+        source: ParseError::MalformedAt {
+          kind,
+          value: value.to_string(),
+          column,
+        },
+      };
+
+      let tokens = tokenize(line.split(COMMENT).next().unwrap_or_default());
+
+      let statement = match tokens[..] {
+        [] => continue,
+        [(column, BLOCK_END)] => {
+          let (block, _, _, statements) = match blocks.len() {
+            1 => return Err(to_error("block", BLOCK_END, column)),
+            _ => blocks.pop().unwrap(),
+          };
+
+          match block {
+            Block::Repeat(count) => Statement::Repeat(count, statements),
+            Block::Macro(name) => {
+              macros.insert(name, statements);
+              continue;
+            }
+            Block::Script => unreachable!(),
+          }
+        }
+        [(column, "repeat"), (count_column, count), (_, BLOCK_START)] => {
+          let count = count
+            .parse()
+            .map_err(|_| to_error("repeat count", count, count_column))?;
+
+          blocks.push((Block::Repeat(count), i + 1, column, vec![]));
+          continue;
+        }
+        [(column, "macro"), (_, name), (_, BLOCK_START)] => {
+          if blocks.len() > 1 {
+            return Err(to_error("macro definition", name, column));
+          }
+
+          blocks.push((Block::Macro(name.to_string()), i + 1, column, vec![]));
+          continue;
+        }
+        [(column, name)] => match macros.get(name) {
+          Some(statements) => Statement::Repeat(1, statements.clone()),
+          None => return Err(to_error("macro call", name, column)),
+        },
+        // directions such as `turn left` span several words, the value is always last
+        [(direction_column, _), .., (value_column, value)] if tokens.len() <= 3 => {
+          let direction = tokens[..tokens.len() - 1]
+            .iter()
+            .map(|(_, token)| *token)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+          Statement::Command(Command {
+            direction: direction
+              .parse::<Direction>()
+              .map_err(|_| to_error("direction", &direction, direction_column))?,
+            value: value
+              .parse()
+              .map_err(|_| to_error("value", value, value_column))?,
+          })
+        }
+        [(column, _), ..] => return Err(to_error("statement", line.trim(), column)),
+      };
+
+      if let Some((_, _, _, statements)) = blocks.last_mut() {
+        statements.push(statement);
+      }
+    }
+
+    match blocks.pop() {
+      Some((Block::Script, _, _, statements)) => Ok(Self(statements)),
+      Some((_, line, column, _)) => Err(InputError::Line {
+        line,
+        source: ParseError::MalformedAt {
+          kind: "unclosed block",
+          value: BLOCK_START.to_string(),
+          column,
+        },
+      }),
+      None => unreachable!(),
+    }
+  }
+
+  pub fn run(&self, console: &mut dyn Console) {
+    run_statements(&self.0, console);
+  }
+}
+
+fn run_statements(statements: &[Statement], console: &mut dyn Console) {
+  for statement in statements.iter() {
+    match statement {
+      Statement::Command(command) => console.interpret(command),
+      Statement::Repeat(count, statements) => {
+        for _ in 0..*count {
+          run_statements(statements, console);
+        }
+      }
+    }
+  }
+}
+
+// Splits a line on whitespaces, keeping the 1-based column of each token.
+fn tokenize(line: &str) -> Vec<(usize, &str)> {
+  let mut tokens = vec![];
+  let mut start = None;
+
+  for (i, c) in line.char_indices().chain([(line.len(), ' ')]) {
+    match (start, c.is_whitespace()) {
+      (None, false) => start = Some(i),
+      (Some(token_start), true) => {
+        tokens.push((
+          line[..token_start].chars().count() + 1,
+          &line[token_start..i],
+        ));
+        start = None;
+      }
+      _ => {}
+    }
+  }
+
+  tokens
+}
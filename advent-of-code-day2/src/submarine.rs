@@ -1,25 +1,90 @@
-use parse_display::FromStr;
+use parse_display::{Display, FromStr};
 use strum::EnumString;
 
-#[derive(EnumString)]
+#[derive(Copy, Clone, Display, EnumString)]
+#[display(style = "lowercase")]
 #[strum(serialize_all = "lowercase")]
 pub enum Direction {
   Forward,
+  Backward,
   Up,
   Down,
+  #[display("turn left")]
+  #[strum(serialize = "turn left")]
+  TurnLeft,
+  #[display("turn right")]
+  #[strum(serialize = "turn right")]
+  TurnRight,
+  #[display("pitch up")]
+  #[strum(serialize = "pitch up")]
+  PitchUp,
+  #[display("pitch down")]
+  #[strum(serialize = "pitch down")]
+  PitchDown,
 }
 
-#[derive(FromStr)]
+#[derive(Copy, Clone, Display, FromStr)]
 #[display("{direction} {value}")]
 pub struct Command {
-  direction: Direction,
-  value: isize,
+  pub direction: Direction,
+  pub value: isize,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum Model {
+  Planar,
+  Aim,
+  Spatial,
+}
+
+impl Model {
+  pub fn create_console(self) -> Box<dyn Console> {
+    match self {
+      Self::Planar => Box::new(Submarine::default()),
+      Self::Aim => Box::new(FixedSubmarine::default()),
+      Self::Spatial => Box::new(SpatialSubmarine::default()),
+    }
+  }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Position {
+  pub x: f64,
+  pub y: f64,
+  pub depth: f64,
+}
+
+// Heading and pitch in degrees, see `SpatialSubmarine`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Orientation {
+  pub heading: f64,
+  pub pitch: f64,
+}
+
+// Turn and pitch commands only steer the spatial model, the planar ones ignore them.
 pub trait Console {
   fn interpret(&mut self, command: &Command);
   fn get_depth(&self) -> isize;
   fn get_horizontal_position(&self) -> isize;
+
+  // only the aim-based model steers, the others always go straight ahead
+  fn get_aim(&self) -> isize {
+    0
+  }
+
+  fn get_position(&self) -> Position {
+    Position {
+      x: self.get_horizontal_position() as f64,
+      y: 0.0,
+      depth: self.get_depth() as f64,
+    }
+  }
+
+  // only the spatial model turns and pitches, the others always face the x axis
+  fn get_orientation(&self) -> Orientation {
+    Orientation::default()
+  }
 }
 
 #[derive(Default)]
@@ -32,8 +97,10 @@ impl Console for Submarine {
   fn interpret(&mut self, command: &Command) {
     match command.direction {
       Direction::Forward => self.horizontal_position += command.value,
+      Direction::Backward => self.horizontal_position -= command.value,
       Direction::Up => self.depth -= command.value,
       Direction::Down => self.depth += command.value,
+      _ => {}
     }
   }
 
@@ -60,8 +127,13 @@ impl Console for FixedSubmarine {
         self.horizontal_position += command.value;
         self.depth += self.aim * command.value;
       }
+      Direction::Backward => {
+        self.horizontal_position -= command.value;
+        self.depth -= self.aim * command.value;
+      }
       Direction::Up => self.aim -= command.value,
       Direction::Down => self.aim += command.value,
+      _ => {}
     }
   }
 
@@ -72,4 +144,63 @@ impl Console for FixedSubmarine {
   fn get_horizontal_position(&self) -> isize {
     self.horizontal_position
   }
+
+  fn get_aim(&self) -> isize {
+    self.aim
+  }
+}
+
+// Moves along its heading (in degrees, counterclockwise from the x axis) and pitch (in degrees,
+// positive when the nose is up), while up and down still dive straight.
+#[derive(Default)]
+pub struct SpatialSubmarine {
+  position: Position,
+  heading: f64,
+  pitch: f64,
+}
+
+impl SpatialSubmarine {
+  fn travel(&mut self, distance: f64) {
+    let (heading, pitch) = (self.heading.to_radians(), self.pitch.to_radians());
+
+    self.position.x += distance * pitch.cos() * heading.cos();
+    self.position.y += distance * pitch.cos() * heading.sin();
+    self.position.depth -= distance * pitch.sin();
+  }
+}
+
+impl Console for SpatialSubmarine {
+  fn interpret(&mut self, command: &Command) {
+    let value = command.value as f64;
+
+    match command.direction {
+      Direction::Forward => self.travel(value),
+      Direction::Backward => self.travel(-value),
+      Direction::Up => self.position.depth -= value,
+      Direction::Down => self.position.depth += value,
+      Direction::TurnLeft => self.heading = (self.heading + value).rem_euclid(360.0),
+      Direction::TurnRight => self.heading = (self.heading - value).rem_euclid(360.0),
+      Direction::PitchUp => self.pitch = (self.pitch + value).min(90.0),
+      Direction::PitchDown => self.pitch = (self.pitch - value).max(-90.0),
+    }
+  }
+
+  fn get_depth(&self) -> isize {
+    self.position.depth.round() as isize
+  }
+
+  fn get_horizontal_position(&self) -> isize {
+    self.position.x.round() as isize
+  }
+
+  fn get_position(&self) -> Position {
+    self.position
+  }
+
+  fn get_orientation(&self) -> Orientation {
+    Orientation {
+      heading: self.heading,
+      pitch: self.pitch,
+    }
+  }
 }
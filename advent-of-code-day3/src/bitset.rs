@@ -1,41 +1,324 @@
-use derive_more::Deref;
-use eyre::eyre;
+use std::iter::FromIterator;
+use std::ops::{BitAnd, BitOr, BitXor, Not};
 use std::str::FromStr;
 
-#[derive(Debug, Deref, Clone)]
-pub struct BitSet(pub(crate) Vec<bool>);
+use strum::EnumString;
+
+use aoc_core::{ParseError, ValidationError};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumString)]
+pub enum Radix {
+  #[strum(serialize = "2")]
+  Binary,
+  #[strum(serialize = "8")]
+  Octal,
+  #[strum(serialize = "16")]
+  Hexadecimal,
+}
+
+impl Radix {
+  const ALL: [Self; 3] = [Self::Binary, Self::Octal, Self::Hexadecimal];
+
+  fn get_value(self) -> u32 {
+    match self {
+      Self::Binary => 2,
+      Self::Octal => 8,
+      Self::Hexadecimal => 16,
+    }
+  }
+
+  fn get_digit_width(self) -> usize {
+    self.get_value().trailing_zeros() as usize
+  }
+
+  fn get_kind(self) -> &'static str {
+    match self {
+      Self::Binary => "binary digit",
+      Self::Octal => "octal digit",
+      Self::Hexadecimal => "hexadecimal digit",
+    }
+  }
+
+  fn get_prefix(self) -> u8 {
+    match self {
+      Self::Binary => b'b',
+      Self::Octal => b'o',
+      Self::Hexadecimal => b'x',
+    }
+  }
+
+  // Strips the prefix of this radix only, as the prefix of another one may be made of digits.
+  pub fn strip_prefix(self, bytes: &[u8]) -> Option<&[u8]> {
+    match bytes {
+      [b'0', prefix, digits @ ..] if prefix.to_ascii_lowercase() == self.get_prefix() => {
+        Some(digits)
+      }
+      _ => None,
+    }
+  }
+
+  // Finds the radix a row is prefixed with, provided the digits after it are valid in that radix,
+  // so that e.g. `0b12` is read as unprefixed hexadecimal digits.
+  pub fn find_prefix(bytes: &[u8]) -> Option<Self> {
+    Self::ALL.iter().copied().find(|radix| {
+      radix.strip_prefix(bytes).map_or(false, |digits| {
+        digits.iter().any(|byte| *byte != b'_')
+          && digits
+            .iter()
+            .all(|byte| *byte == b'_' || (*byte as char).is_digit(radix.get_value()))
+      })
+    })
+  }
+
+  // Picks the smallest radix fitting every digit of the rows without a prefix.
+  pub fn detect(rows: &[&[u8]]) -> Self {
+    let digits = rows
+      .iter()
+      .filter(|row| Self::find_prefix(row).is_none())
+      .flat_map(|row| row.iter())
+      .filter(|byte| **byte != b'_');
+
+    Self::ALL
+      .iter()
+      .copied()
+      .find(|radix| {
+        digits
+          .clone()
+          .all(|byte| (*byte as char).is_digit(radix.get_value()))
+      })
+      .unwrap_or(Self::Hexadecimal)
+  }
+}
+
+// Bits are packed in 64-bit words, the first bit being the most significant one of the number the
+// set stands for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+  words: Vec<u64>,
+  len: usize,
+}
 
 impl FromStr for BitSet {
-  type Err = aoc_core::Error;
+  type Err = ParseError;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    Ok(Self(
-      s.chars()
-        .enumerate()
-        .map(|(i, c)| match c {
-          '0' => Ok(false),
-          '1' => Ok(true),
-          _ => Err(eyre!("Invalid bit '{}' value at {} index", c, i)),
-        })
-        .collect::<aoc_core::Result<Vec<_>>>()?,
-    ))
+    Self::from_bytes(s.as_bytes())
   }
 }
 
 impl BitSet {
-  pub fn to_usize(&self) -> usize {
-    self.0.iter().fold(0_usize, |acc, v| {
-      let v = if *v { 1 } else { 0 };
+  pub fn new(len: usize) -> Self {
+    Self {
+      words: vec![0; (len + WORD_BITS - 1) / WORD_BITS],
+      len,
+    }
+  }
 
-      (acc << 1) + v
-    })
+  pub fn from_bytes(bytes: &[u8]) -> Result<Self, ParseError> {
+    let mut bit_set = Self::new(bytes.len());
+
+    for (i, byte) in bytes.iter().enumerate() {
+      match byte {
+        b'0' => {}
+        b'1' => bit_set.set(i, true),
+        _ => {
+          return Err(ParseError::InvalidCharAt {
+            kind: "bit",
+            value: *byte as char,
+            column: i + 1,
+          })
+        }
+      }
+    }
+
+    Ok(bit_set)
+  }
+
+  // Parses the digits of a row, optionally prefixed and separated by underscores, each digit
+  // standing for as many bits as the radix needs so that rows keep a fixed width.
+  pub fn from_digits(bytes: &[u8], radix: Radix) -> Result<Self, ParseError> {
+    let (offset, digits) = match radix.strip_prefix(bytes) {
+      Some(digits) => (2, digits),
+      None => (0, bytes),
+    };
+
+    let digit_width = radix.get_digit_width();
+    let len = digits.iter().filter(|byte| **byte != b'_').count() * digit_width;
+    let mut bit_set = Self::new(len);
+    let mut i = 0;
+
+    for (column, byte) in digits.iter().enumerate() {
+      if *byte == b'_' {
+        continue;
+      }
+
+      let digit = (*byte as char)
+        .to_digit(radix.get_value())
+        .ok_or(ParseError::InvalidCharAt {
+          kind: radix.get_kind(),
+          value: *byte as char,
+          column: offset + column + 1,
+        })?;
+
+      for bit in 0..digit_width {
+        bit_set.set(i + bit, digit & (1 << (digit_width - 1 - bit)) != 0);
+      }
+
+      i += digit_width;
+    }
+
+    Ok(bit_set)
+  }
+
+  pub fn get_width(&self) -> usize {
+    self.len
+  }
+
+  pub fn get(&self, i: usize) -> bool {
+    i < self.len && self.words[i / WORD_BITS] & (1 << (i % WORD_BITS)) != 0
+  }
+
+  pub fn set(&mut self, i: usize, bit: bool) {
+    let mask = 1 << (i % WORD_BITS);
+
+    if bit {
+      self.words[i / WORD_BITS] |= mask;
+    } else {
+      self.words[i / WORD_BITS] &= !mask;
+    }
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+    (0..self.len).map(move |i| self.get(i))
+  }
+
+  // Reads the bits as an unsigned number, the leading zeros not counting towards its size.
+  pub fn to_u128(&self) -> Result<u128, ValidationError> {
+    let leading_zeros = self.iter().take_while(|bit| !bit).count();
+
+    if self.len - leading_zeros > u128::BITS as usize {
+      return Err(ValidationError::Overflow {
+        kind: "row value",
+        bits: u128::BITS,
+      });
+    }
+
+    Ok(
+      self
+        .iter()
+        .skip(leading_zeros)
+        .fold(0_u128, |acc, bit| (acc << 1) | u128::from(bit)),
+    )
+  }
+
+  // Counts the ones of each column over all the bit sets, only visiting the bits which are set.
+  pub fn count_columns<'a>(
+    bit_sets: impl IntoIterator<Item = &'a BitSet>,
+    len: usize,
+  ) -> Vec<usize> {
+    let mut counters = vec![0; len];
+
+    for bit_set in bit_sets {
+      for (i, word) in bit_set.words.iter().enumerate() {
+        let mut word = *word;
+
+        while word != 0 {
+          let column = i * WORD_BITS + word.trailing_zeros() as usize;
+
+          if let Some(counter) = counters.get_mut(column) {
+            *counter += 1;
+          }
+
+          word &= word - 1;
+        }
+      }
+    }
+
+    counters
+  }
+
+  fn zip_with(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+    Self {
+      words: self
+        .words
+        .iter()
+        .zip(other.words.iter().chain(std::iter::repeat(&0)))
+        .map(|(a, b)| f(*a, *b))
+        .collect(),
+      len: self.len,
+    }
+  }
+}
+
+impl FromIterator<bool> for BitSet {
+  fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+    let mut bit_set = Self::new(0);
+
+    for bit in iter {
+      if bit_set.len % WORD_BITS == 0 {
+        bit_set.words.push(0);
+      }
+
+      bit_set.len += 1;
+      bit_set.set(bit_set.len - 1, bit);
+    }
+
+    bit_set
+  }
+}
+
+impl BitAnd for &BitSet {
+  type Output = BitSet;
+
+  fn bitand(self, other: Self) -> BitSet {
+    self.zip_with(other, |a, b| a & b)
+  }
+}
+
+impl BitOr for &BitSet {
+  type Output = BitSet;
+
+  fn bitor(self, other: Self) -> BitSet {
+    self.zip_with(other, |a, b| a | b)
+  }
+}
+
+impl BitXor for &BitSet {
+  type Output = BitSet;
+
+  fn bitxor(self, other: Self) -> BitSet {
+    self.zip_with(other, |a, b| a ^ b)
+  }
+}
+
+impl Not for &BitSet {
+  type Output = BitSet;
+
+  fn not(self) -> BitSet {
+    let mut bit_set = BitSet {
+      words: self.words.iter().map(|word| !word).collect(),
+      len: self.len,
+    };
+
+    // bits past the length have to stay cleared
+    if let Some(word) = bit_set.words.last_mut() {
+      let used_bits = self.len % WORD_BITS;
+
+      if used_bits > 0 {
+        *word &= (1 << used_bits) - 1;
+      }
+    }
+
+    bit_set
   }
 }
 
 impl std::fmt::Display for BitSet {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    for bit in self.0.iter() {
-      write!(f, "{}", *bit as usize)?;
+    for bit in self.iter() {
+      write!(f, "{}", bit as usize)?;
     }
 
     Ok(())
@@ -0,0 +1,159 @@
+use std::fmt;
+
+use parse_display::{Display, FromStr};
+
+use aoc_core::ValidationError;
+
+use crate::bitset::BitSet;
+use crate::partition::SortedRows;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display, FromStr)]
+#[display(style = "lowercase")]
+pub enum Commonality {
+  Most,
+  Least,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display, FromStr)]
+#[display(style = "lowercase")]
+pub enum Tie {
+  Zero,
+  One,
+  // do not filter on a column with as many ones as zeros
+  #[display("keep")]
+  KeepBoth,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display, FromStr)]
+#[display(style = "lowercase")]
+pub enum Scan {
+  Msb,
+  Lsb,
+}
+
+// Keeps the rows having the most or least common bit of each column, e.g. `most:one:msb`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display, FromStr)]
+#[display("{commonality}:{tie}:{scan}")]
+pub struct BitCriteria {
+  pub commonality: Commonality,
+  pub tie: Tie,
+  pub scan: Scan,
+}
+
+impl BitCriteria {
+  pub const OXYGEN_GENERATOR: Self = Self {
+    commonality: Commonality::Most,
+    tie: Tie::One,
+    scan: Scan::Msb,
+  };
+
+  pub const CO2_SCRUBBER: Self = Self {
+    commonality: Commonality::Least,
+    tie: Tie::Zero,
+    scan: Scan::Msb,
+  };
+
+  pub fn get_columns(&self, width: usize) -> Vec<usize> {
+    match self.scan {
+      Scan::Msb => (0..width).collect(),
+      Scan::Lsb => (0..width).rev().collect(),
+    }
+  }
+
+  // Narrows down the sorted rows, which have to follow the columns in the scan order of the
+  // criteria.
+  pub fn apply<'a>(&self, sorted_rows: &SortedRows<'a>) -> Rating<'a> {
+    let mut ranges = vec![sorted_rows.get_all()];
+    let mut trace = vec![];
+
+    for column in sorted_rows.get_columns() {
+      let rows = sorted_rows.count_rows(&ranges);
+
+      if rows <= 1 {
+        break;
+      }
+
+      let ones = sorted_rows.count_bits(&ranges, *column, true);
+      let zeros = rows - ones;
+
+      let bit = if ones == zeros {
+        match self.tie {
+          Tie::Zero => Some(false),
+          Tie::One => Some(true),
+          Tie::KeepBoth => None,
+        }
+      } else {
+        Some((ones > zeros) == (self.commonality == Commonality::Most))
+      };
+
+      // a bit no row has would eliminate them all, so the column is skipped instead
+      let bit = bit.filter(|bit| if *bit { ones > 0 } else { zeros > 0 });
+
+      ranges = sorted_rows.split(&ranges, *column, bit);
+
+      trace.push(Step {
+        column: *column,
+        ones,
+        zeros,
+        bit,
+        remaining: sorted_rows.count_rows(&ranges),
+      });
+    }
+
+    Rating {
+      criteria: *self,
+      row: sorted_rows
+        .get_first_row(&ranges)
+        .map(|row| &sorted_rows.get_bit_sets()[row]),
+      trace,
+    }
+  }
+}
+
+pub struct Step {
+  pub column: usize,
+  pub ones: usize,
+  pub zeros: usize,
+  // the bit the remaining rows have, none when the column did not filter anything
+  pub bit: Option<bool>,
+  pub remaining: usize,
+}
+
+pub struct Rating<'a> {
+  pub criteria: BitCriteria,
+  pub row: Option<&'a BitSet>,
+  pub trace: Vec<Step>,
+}
+
+impl Rating<'_> {
+  pub fn get_value(&self) -> Result<u128, ValidationError> {
+    self.row.map_or(Ok(0), BitSet::to_u128)
+  }
+}
+
+impl fmt::Display for Rating<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.row {
+      Some(row) => match self.get_value() {
+        Ok(value) => write!(f, "{}: {} ({})", self.criteria, row, value)?,
+        Err(_) => write!(f, "{}: {}", self.criteria, row)?,
+      },
+      None => write!(f, "{}: no row", self.criteria)?,
+    }
+
+    for step in self.trace.iter() {
+      let bit = match step.bit {
+        Some(bit) => (bit as usize).to_string(),
+        None => "both".to_string(),
+      };
+
+      write!(
+        f,
+        "\n  bit {}: {} one(s), {} zero(s), kept {}, {} row(s) left",
+        step.column, step.ones, step.zeros, bit, step.remaining
+      )?;
+    }
+
+    Ok(())
+  }
+}
@@ -1,81 +1,352 @@
 mod bitset;
+mod criteria;
+mod partition;
 mod report;
+mod stats;
 
-use std::fs::File;
-use std::path::PathBuf;
-
-use aoc_core::{Puzzle, Result};
+use aoc_core::{Puzzle, Result, Runner, RunnerOpt};
 use structopt::StructOpt;
 
-use report::Report;
+use bitset::Radix;
+use criteria::BitCriteria;
+use report::{RaggedRows, Report, ReportLayout};
 
 #[derive(StructOpt)]
 struct Opt {
+  #[structopt(flatten)]
+  runner: RunnerOpt,
+  /// Number of bits per row, defaults to the row widths found in the report
+  #[structopt(long)]
+  width: Option<usize>,
+  /// Reject or pad rows narrower than the report width
+  #[structopt(long, default_value = "reject")]
+  ragged_rows: RaggedRows,
+  /// Base of the row digits (2, 8 or 16), detected from the report by default
+  #[structopt(long)]
+  radix: Option<Radix>,
+  /// Also print the row kept by these `<most|least>:<zero|one|keep>:<msb|lsb>` criteria
+  #[structopt(long, number_of_values = 1)]
+  rating: Vec<BitCriteria>,
+  /// Print statistics about each bit column instead of solving the puzzle
+  #[structopt(long)]
+  stats: bool,
   #[structopt(short, long)]
-  filename: PathBuf,
-  #[structopt(short, long)]
-  puzzle: Puzzle,
+  debug: bool,
 }
 
-fn solve_puzzle(report: Report, puzzle: Puzzle) -> usize {
+fn solve_puzzle(
+  report: Report,
+  puzzle: Puzzle,
+  debug: bool,
+  ratings: &[BitCriteria],
+) -> Result<u128> {
   let report = report.decode();
+  let puzzle_ratings = [BitCriteria::OXYGEN_GENERATOR, BitCriteria::CO2_SCRUBBER];
 
-  if let Puzzle::Part1 = puzzle {
-    report.compute_power_consumption()
-  } else {
-    report.compute_life_support_rating()
+  for criteria in puzzle_ratings
+    .iter()
+    .filter(|_| debug)
+    .chain(ratings.iter())
+  {
+    println!("{}", report.rate(*criteria));
   }
+
+  let answer = if let Puzzle::Part1 = puzzle {
+    report.compute_power_consumption()?
+  } else {
+    report.compute_life_support_rating()?
+  };
+
+  Ok(answer)
 }
 
 fn main() -> Result<()> {
   color_eyre::install()?;
 
   let opt = Opt::from_args();
-  let report = Report::from_reader(File::open(opt.filename)?)?;
-  let result = solve_puzzle(report, opt.puzzle);
+  let debug = opt.debug;
+  let ratings = opt.rating;
+  let layout = ReportLayout {
+    width: opt.width,
+    ragged_rows: opt.ragged_rows,
+    radix: opt.radix,
+  };
+
+  let runner = Runner::new("day3", opt.runner)?;
 
-  println!("{}", result);
+  if opt.stats {
+    runner.run(move |input, _| {
+      Ok(
+        Report::from_input(&input, layout)?
+          .decode()
+          .analyze_columns(),
+      )
+    })
+  } else {
+    runner.run(move |input, puzzle| {
+      let report = Report::from_input(&input, layout)?;
 
-  Ok(())
+      solve_puzzle(report, puzzle, debug, &ratings)
+    })
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
 
-  const EXAMPLE_DATA: &str = r"00100
-11110
-10110
-10111
-10101
-01111
-00111
-11100
-10000
-11001
-00010
-01010
-";
-
-  const EXAMPLE_RESPONSE_PART1: usize = 198;
-  const EXAMPLE_RESPONSE_PART2: usize = 230;
+  use aoc_core::{Input, InputError, ValidationError};
+
+  use aoc_core::ParseError;
+  use bitset::BitSet;
+  use stats::Relation;
+
+  aoc_test::example_tests!(|input: &str, puzzle| {
+    let report = Report::from_input(&Input::from(input), ReportLayout::default())?;
+
+    solve_puzzle(report, puzzle, true, &[])
+  });
+
+  #[test]
+  fn it_packs_bits_across_words() -> Result<()> {
+    let a = format!("1{}01", "0".repeat(67)).parse::<BitSet>()?;
+    let b = format!("1{}10", "0".repeat(67)).parse::<BitSet>()?;
+
+    assert_eq!(a.get_width(), 70);
+    assert!(a.get(0) && a.get(69) && !a.get(68));
+    assert_eq!(
+      BitSet::count_columns([&a, &b], 70)
+        .into_iter()
+        .enumerate()
+        .filter(|(_, counter)| *counter > 0)
+        .collect::<Vec<_>>(),
+      vec![(0, 2), (68, 1), (69, 1)]
+    );
+    assert_eq!((&a & &b).to_string(), format!("1{}", "0".repeat(69)));
+    assert_eq!((&a | &b).to_string(), format!("1{}11", "0".repeat(67)));
+    assert_eq!((&a ^ &b).to_string(), format!("{}11", "0".repeat(68)));
+    assert_eq!((!&a).to_string(), format!("0{}10", "1".repeat(67)));
+
+    Ok(())
+  }
 
   #[test]
-  fn it_solves_example_input_part1() -> Result<()> {
-    let report = Report::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(report, Puzzle::Part1);
+  fn it_computes_epsilon_over_report_width() -> Result<()> {
+    let report = Report::from_input(&Input::from("011\n001\n010\n"), ReportLayout::default())?;
+
+    assert_eq!(report.decode().compute_power_consumption()?, 0b011 * 0b100);
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_computes_values_of_wide_rows() -> Result<()> {
+    let decode = |input: &str| -> Result<_> {
+      Ok(Report::from_input(&Input::from(input), ReportLayout::default())?.decode())
+    };
+
+    // 40 bits, the product needs 79 of them
+    let report = decode(&format!("{}\n", "10".repeat(20)))?;
 
-    assert_eq!(EXAMPLE_RESPONSE_PART1, result);
+    assert_eq!(
+      report.compute_power_consumption()?,
+      0xaa_aaaa_aaaa * 0x55_5555_5555
+    );
+
+    // 70 bits, leading zeros do not count towards the value size
+    let report = decode(&format!("{}1{}\n", "0".repeat(60), "0".repeat(9)))?;
+
+    assert_eq!(report.compute_life_support_rating()?, 512 * 512);
+    assert_eq!(
+      report.compute_power_consumption()?,
+      512 * ((1 << 70) - 1 - 512)
+    );
+
+    let report = decode(&format!("1{}\n", "0".repeat(69)))?;
+
+    assert!(matches!(
+      report.compute_power_consumption(),
+      Err(ValidationError::Overflow { .. })
+    ));
+
+    // 130 bits, the value itself no longer fits
+    let report = decode(&format!("1{}\n", "0".repeat(129)))?;
+
+    assert!(matches!(
+      report.compute_life_support_rating(),
+      Err(ValidationError::Overflow { .. })
+    ));
 
     Ok(())
   }
 
   #[test]
-  fn it_solves_examples_input_part2() -> Result<()> {
-    let report = Report::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(report, Puzzle::Part2);
+  fn it_handles_ragged_rows() -> Result<()> {
+    let input = Input::from("0101\n11\n0110\n");
+    let layout = ReportLayout {
+      width: None,
+      ragged_rows: RaggedRows::Pad,
+      radix: None,
+    };
+
+    // 11 is padded to 0011
+    assert_eq!(
+      Report::from_input(&input, layout)?
+        .decode()
+        .compute_power_consumption()?,
+      0b0111 * 0b1000
+    );
+
+    assert!(matches!(
+      Report::from_input(&input, ReportLayout::default()),
+      Err(InputError::LineValidation {
+        line: 2,
+        source: ValidationError::Size {
+          expected: 4,
+          actual: 2,
+          ..
+        }
+      })
+    ));
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_renders_rating_traces() -> Result<()> {
+    let input = Input::from(aoc_test::example!("example").input.as_str());
+    let report = Report::from_input(&input, ReportLayout::default())?.decode();
+    let traces = ["most:one:msb", "least:keep:lsb"]
+      .iter()
+      .map(|criteria| Ok(report.rate(criteria.parse()?).to_string()))
+      .collect::<Result<Vec<_>>>()?;
+
+    insta::assert_snapshot!(traces.join("\n"));
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_rates_duplicate_rows_through_the_sorted_rows() -> Result<()> {
+    let input = Input::from("101\n011\n101\n000\n");
+    let report = Report::from_input(&input, ReportLayout::default())?.decode();
+
+    // both 101 rows survive every column, the first one is rated
+    let rating = report.rate(BitCriteria::OXYGEN_GENERATOR);
+
+    assert_eq!(rating.get_value()?, 0b101);
+    assert_eq!(rating.trace.len(), 3);
+    assert_eq!(rating.trace[2].remaining, 2);
+    assert_eq!(report.rate("most:one:lsb".parse()?).get_value()?, 0b101);
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_parses_multi_radix_rows() -> Result<()> {
+    let input = Input::from("0x1f\n0b0001_0000\n0o17\n");
+    let layout = ReportLayout {
+      width: None,
+      ragged_rows: RaggedRows::Pad,
+      radix: None,
+    };
+    let report = Report::from_input(&input, layout)?.decode();
+
+    assert_eq!(
+      report
+        .bit_sets
+        .iter()
+        .map(|bit_set| Ok((bit_set.get_width(), bit_set.to_u128()?)))
+        .collect::<Result<Vec<_>>>()?,
+      vec![(8, 0x1f), (8, 0b10000), (8, 0o17)]
+    );
+
+    // 'f' rules out octal, so 'g' is an invalid hexadecimal digit
+    assert!(matches!(
+      Report::from_input(&Input::from("1f\n2_g\n"), ReportLayout::default()),
+      Err(InputError::Line {
+        line: 2,
+        source: ParseError::InvalidCharAt {
+          value: 'g',
+          column: 3,
+          ..
+        }
+      })
+    ));
+    assert_eq!(
+      BitSet::from_digits(b"2_g", Radix::Hexadecimal)
+        .err()
+        .map(|e| e.to_string()),
+      Some("invalid 'g' hexadecimal digit at column 3".to_string())
+    );
+
+    let layout = ReportLayout {
+      radix: Some(Radix::Octal),
+      ..ReportLayout::default()
+    };
+
+    // only the octal prefix is stripped, so the hexadecimal one is read as digits
+    assert!(matches!(
+      Report::from_input(&Input::from("17\n0x1f\n"), layout),
+      Err(InputError::Line {
+        line: 2,
+        source: ParseError::InvalidCharAt {
+          value: 'x',
+          column: 2,
+          ..
+        }
+      })
+    ));
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_reads_prefix_like_hexadecimal_digits() -> Result<()> {
+    let values = |layout| -> Result<Vec<u128>> {
+      let report = Report::from_input(&Input::from("0b12\n0x0b12\nff00\n"), layout)?.decode();
+
+      report
+        .bit_sets
+        .iter()
+        .map(|bit_set| Ok(bit_set.to_u128()?))
+        .collect()
+    };
+    let layout = ReportLayout {
+      radix: Some(Radix::Hexadecimal),
+      ..ReportLayout::default()
+    };
+
+    // `0b` is not followed by binary digits, so it is not a prefix
+    assert_eq!(
+      values(ReportLayout::default())?,
+      vec![0x0b12, 0x0b12, 0xff00]
+    );
+    assert_eq!(values(layout)?, vec![0x0b12, 0x0b12, 0xff00]);
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_renders_column_stats() -> Result<()> {
+    let input = Input::from(aoc_test::example!("example").input.as_str());
+    let report = Report::from_input(&input, ReportLayout::default())?.decode();
+
+    insta::assert_snapshot!(aoc_test::render(&report.analyze_columns()));
+
+    let input = Input::from("1100\n0011\n1010\n0101\n");
+    let pairs = Report::from_input(&input, ReportLayout::default())?
+      .decode()
+      .analyze_columns()
+      .pairs;
 
-    assert_eq!(EXAMPLE_RESPONSE_PART2, result);
+    assert_eq!(
+      pairs
+        .iter()
+        .map(|pair| (pair.columns, pair.relation))
+        .collect::<Vec<_>>(),
+      vec![((0, 3), Relation::Inverted), ((1, 2), Relation::Inverted)]
+    );
 
     Ok(())
   }
@@ -0,0 +1,85 @@
+use std::ops::Range;
+
+use crate::bitset::BitSet;
+
+// Rows sorted on their columns in the given order: the rows sharing the same first bits lie in
+// the same range, which the next column splits in two, zeros first.
+pub struct SortedRows<'a> {
+  bit_sets: &'a [BitSet],
+  columns: Vec<usize>,
+  rows: Vec<usize>,
+}
+
+impl<'a> SortedRows<'a> {
+  pub fn new(bit_sets: &'a [BitSet], columns: Vec<usize>) -> Self {
+    let mut rows = (0..bit_sets.len()).collect::<Vec<_>>();
+
+    // the sort is stable, so identical rows stay in their original order
+    rows.sort_by(|a, b| {
+      let bits = |row: usize| columns.iter().map(move |column| bit_sets[row].get(*column));
+
+      bits(*a).cmp(bits(*b))
+    });
+
+    Self {
+      bit_sets,
+      columns,
+      rows,
+    }
+  }
+
+  pub fn get_bit_sets(&self) -> &'a [BitSet] {
+    self.bit_sets
+  }
+
+  pub fn get_columns(&self) -> &[usize] {
+    &self.columns
+  }
+
+  pub fn get_all(&self) -> Range<usize> {
+    0..self.rows.len()
+  }
+
+  pub fn count_rows(&self, ranges: &[Range<usize>]) -> usize {
+    ranges.iter().map(|range| range.len()).sum()
+  }
+
+  pub fn count_bits(&self, ranges: &[Range<usize>], column: usize, bit: bool) -> usize {
+    self.count_rows(&self.split(ranges, column, Some(bit)))
+  }
+
+  // Splits every range on the given column, keeping the part having the given bit, or both parts
+  // without one.
+  pub fn split(
+    &self,
+    ranges: &[Range<usize>],
+    column: usize,
+    bit: Option<bool>,
+  ) -> Vec<Range<usize>> {
+    ranges
+      .iter()
+      .flat_map(|range| {
+        let pivot = range.start
+          + self.rows[range.clone()].partition_point(|row| !self.bit_sets[*row].get(column));
+
+        match bit {
+          Some(false) => [Some(range.start..pivot), None],
+          Some(true) => [Some(pivot..range.end), None],
+          None => [Some(range.start..pivot), Some(pivot..range.end)],
+        }
+      })
+      .flatten()
+      .filter(|range| !range.is_empty())
+      .collect()
+  }
+
+  // The first row of a range is its first original row once every column split it, as identical
+  // rows kept their order.
+  pub fn get_first_row(&self, ranges: &[Range<usize>]) -> Option<usize> {
+    ranges
+      .iter()
+      .filter(|range| !range.is_empty())
+      .map(|range| self.rows[range.start])
+      .min()
+  }
+}
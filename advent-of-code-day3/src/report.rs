@@ -1,108 +1,171 @@
-use std::io::Read;
+use strum::EnumString;
 
-use aoc_core::Result;
+use aoc_core::{Input, InputError, ValidationError};
 
-use crate::bitset::BitSet;
+use crate::bitset::{BitSet, Radix};
+use crate::criteria::{BitCriteria, Rating};
+use crate::partition::SortedRows;
+use crate::stats::ColumnReport;
 
-pub struct Report(Vec<BitSet>);
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum RaggedRows {
+  Reject,
+  // left-pad the shorter rows with zeros, which keeps their value
+  Pad,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ReportLayout {
+  // defaults to the first row width when rejecting ragged rows, to the widest row when padding them
+  pub width: Option<usize>,
+  pub ragged_rows: RaggedRows,
+  // detected from the row prefixes, or the digits of the rows without one, when not given
+  pub radix: Option<Radix>,
+}
+
+impl Default for ReportLayout {
+  fn default() -> Self {
+    Self {
+      width: None,
+      ragged_rows: RaggedRows::Reject,
+      radix: None,
+    }
+  }
+}
+
+pub struct Report {
+  width: usize,
+  bit_sets: Vec<BitSet>,
+}
 
 impl Report {
-  pub fn from_reader(reader: impl Read) -> Result<Self> {
-    let bit_sets = aoc_core::read_lines(reader)?;
+  pub fn from_input(input: &Input, layout: ReportLayout) -> Result<Self, InputError> {
+    let lines = input.lines().collect::<Vec<_>>();
+    let detected_radix = Radix::detect(&lines);
+    let bit_sets = lines
+      .into_iter()
+      .enumerate()
+      .map(|(i, line)| {
+        let radix = layout
+          .radix
+          .or_else(|| Radix::find_prefix(line))
+          .unwrap_or(detected_radix);
+
+        BitSet::from_digits(line, radix).map_err(|source| InputError::Line {
+          line: i + 1,
+          source,
+        })
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    Self::from_bit_sets(bit_sets, layout)
+  }
 
-    Ok(Self(bit_sets))
+  fn from_bit_sets(bit_sets: Vec<BitSet>, layout: ReportLayout) -> Result<Self, InputError> {
+    let mut widths = bit_sets.iter().map(BitSet::get_width);
+    let width = match (layout.width, layout.ragged_rows) {
+      (Some(width), _) => Some(width),
+      (None, RaggedRows::Reject) => widths.next(),
+      (None, RaggedRows::Pad) => widths.max(),
+    }
+    .filter(|width| *width > 0)
+    .ok_or(ValidationError::Empty { kind: "report" })?;
+
+    let bit_sets = bit_sets
+      .into_iter()
+      .enumerate()
+      .map(|(i, bit_set)| match bit_set.get_width() {
+        actual if actual == width => Ok(bit_set),
+        actual if actual < width && layout.ragged_rows == RaggedRows::Pad => Ok(
+          std::iter::repeat(false)
+            .take(width - actual)
+            .chain(bit_set.iter())
+            .collect(),
+        ),
+        actual => Err(InputError::LineValidation {
+          line: i + 1,
+          source: ValidationError::Size {
+            kind: "row",
+            expected: width,
+            actual,
+          },
+        }),
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Self { width, bit_sets })
   }
 
   pub fn decode(self) -> DecodedReport {
-    let max_bit_set_len = self
-      .0
-      .iter()
-      .map(|bit_set| bit_set.len())
-      .max()
-      .unwrap_or_default();
-
-    let bit_counters = self
-      .0
-      .iter()
-      .fold(vec![0; max_bit_set_len], |mut bit_counters, bit_set| {
-        for (i, bit) in bit_set.iter().enumerate() {
-          bit_counters[i] += usize::from(*bit);
-        }
-
-        bit_counters
-      });
-
-    // pivot value to consider a bit as the most common bit
-    let bit_counter_pivot = (self.0.len() as f32 / 2.0).ceil() as usize;
-
-    let most_common_bits = BitSet(
-      bit_counters
-        .into_iter()
-        .map(|counter| counter >= bit_counter_pivot)
-        .collect(),
-    );
+    let bit_counters = BitSet::count_columns(&self.bit_sets, self.width);
 
     DecodedReport {
-      most_common_bits,
-      bit_sets: self.0,
+      most_common_bits: find_most_common_bits(&bit_counters, self.bit_sets.len()),
+      bit_counters,
+      bit_sets: self.bit_sets,
     }
   }
 }
 
 pub struct DecodedReport {
   pub(crate) most_common_bits: BitSet,
+  // number of ones in each column
+  bit_counters: Vec<usize>,
   pub(crate) bit_sets: Vec<BitSet>,
 }
 
 impl DecodedReport {
-  pub fn compute_power_consumption(&self) -> usize {
-    let gamma_rate = self
-      .most_common_bits
-      .iter()
-      .fold(0_usize, |gamma_rate, bit| {
-        let bit = if *bit { 1 } else { 0 };
-
-        (gamma_rate << 1) + bit
-      });
-
-    let epsilon_rate = {
-      let mask = (1 << (usize::BITS - gamma_rate.leading_zeros())) - 1;
+  pub fn compute_power_consumption(&self) -> Result<u128, ValidationError> {
+    let gamma_rate = self.most_common_bits.to_u128()?;
+    // the least common bits, over the whole report width
+    let epsilon_rate = (!&self.most_common_bits).to_u128()?;
+
+    gamma_rate
+      .checked_mul(epsilon_rate)
+      .ok_or(ValidationError::Overflow {
+        kind: "power consumption",
+        bits: u128::BITS,
+      })
+  }
 
-      gamma_rate ^ mask
-    };
+  pub fn compute_life_support_rating(&self) -> Result<u128, ValidationError> {
+    // both ratings scan the most significant bit first, so they share the same sorted rows
+    let sorted_rows = self.sort_rows(BitCriteria::OXYGEN_GENERATOR);
+    let oxigen_generator_rating = BitCriteria::OXYGEN_GENERATOR
+      .apply(&sorted_rows)
+      .get_value()?;
+    let c02_scrubber_rating = BitCriteria::CO2_SCRUBBER.apply(&sorted_rows).get_value()?;
+
+    oxigen_generator_rating
+      .checked_mul(c02_scrubber_rating)
+      .ok_or(ValidationError::Overflow {
+        kind: "life support rating",
+        bits: u128::BITS,
+      })
+  }
 
-    gamma_rate * epsilon_rate
+  pub fn analyze_columns(&self) -> ColumnReport {
+    ColumnReport::new(&self.bit_counters, &self.bit_sets)
   }
 
-  pub fn compute_life_support_rating(&self) -> usize {
-    let oxigen_generator_rating = compute_life_support_rating(|bit| bit, 0, self);
-    let c02_scrubber_rating = compute_life_support_rating(|bit| !bit, 0, self);
+  pub fn rate(&self, criteria: BitCriteria) -> Rating<'_> {
+    criteria.apply(&self.sort_rows(criteria))
+  }
 
-    oxigen_generator_rating * c02_scrubber_rating
+  // Only sorted on demand, as neither the power consumption nor the column stats need it.
+  fn sort_rows(&self, criteria: BitCriteria) -> SortedRows<'_> {
+    SortedRows::new(
+      &self.bit_sets,
+      criteria.get_columns(self.most_common_bits.get_width()),
+    )
   }
 }
 
-fn compute_life_support_rating(
-  f: impl Fn(bool) -> bool,
-  i: usize,
-  report: &DecodedReport,
-) -> usize {
-  let bit = f(report.most_common_bits[i]);
-
-  let found_bit_sets = report
-    .bit_sets
+fn find_most_common_bits(bit_counters: &[usize], rows: usize) -> BitSet {
+  // a bit is the most common bit when there are at least as many ones as zeros
+  bit_counters
     .iter()
-    .filter(|bit_set| bit_set[i] == bit)
-    .cloned()
-    .collect::<Vec<_>>();
-
-  match found_bit_sets.len() {
-    1 => found_bit_sets[0].to_usize(),
-    0 => compute_life_support_rating(f, i + 1, report),
-    _ => {
-      let report = Report(found_bit_sets).decode();
-
-      compute_life_support_rating(f, i + 1, &report)
-    }
-  }
+    .map(|counter| *counter >= rows - counter)
+    .collect()
 }
@@ -0,0 +1,148 @@
+use std::fmt;
+
+use parse_display::Display;
+use serde::Serialize;
+
+use crate::bitset::BitSet;
+
+#[derive(Debug, Serialize)]
+pub struct ColumnStats {
+  pub column: usize,
+  pub ones: usize,
+  pub ratio: f64,
+  // Shannon entropy in bits, from 0 for a constant column to 1 for a tie
+  pub entropy: f64,
+  pub tie: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Display, Serialize)]
+#[display(style = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum Relation {
+  Correlated,
+  Inverted,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColumnPair {
+  pub columns: (usize, usize),
+  pub relation: Relation,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColumnReport {
+  pub rows: usize,
+  pub columns: Vec<ColumnStats>,
+  pub pairs: Vec<ColumnPair>,
+}
+
+impl ColumnReport {
+  pub fn new(bit_counters: &[usize], bit_sets: &[BitSet]) -> Self {
+    let rows = bit_sets.len();
+
+    let columns = bit_counters
+      .iter()
+      .enumerate()
+      .map(|(column, ones)| {
+        let ratio = *ones as f64 / rows as f64;
+
+        ColumnStats {
+          column,
+          ones: *ones,
+          ratio,
+          entropy: compute_entropy(ratio),
+          tie: *ones * 2 == rows,
+        }
+      })
+      .collect();
+
+    Self {
+      rows,
+      columns,
+      pairs: find_column_pairs(bit_counters, bit_sets),
+    }
+  }
+}
+
+fn compute_entropy(ratio: f64) -> f64 {
+  [ratio, 1.0 - ratio]
+    .iter()
+    .filter(|p| **p > 0.0)
+    .map(|p| -p * p.log2())
+    .sum()
+}
+
+// Compares the columns as bit sets over the rows. Constant columns are left out as any two of them
+// would trivially match.
+fn find_column_pairs(bit_counters: &[usize], bit_sets: &[BitSet]) -> Vec<ColumnPair> {
+  let columns = bit_counters
+    .iter()
+    .enumerate()
+    .filter(|(_, ones)| **ones > 0 && **ones < bit_sets.len())
+    .map(|(column, _)| {
+      let bits = bit_sets.iter().map(|bit_set| bit_set.get(column)).collect();
+
+      (column, bits)
+    })
+    .collect::<Vec<(usize, BitSet)>>();
+
+  let mut pairs = vec![];
+
+  for (i, (first, first_bits)) in columns.iter().enumerate() {
+    for (second, second_bits) in columns[i + 1..].iter() {
+      let relation = if first_bits == second_bits {
+        Relation::Correlated
+      } else if &!first_bits == second_bits {
+        Relation::Inverted
+      } else {
+        continue;
+      };
+
+      pairs.push(ColumnPair {
+        columns: (*first, *second),
+        relation,
+      });
+    }
+  }
+
+  pairs
+}
+
+impl fmt::Display for ColumnReport {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(
+      f,
+      "{:>6} {:>8} {:>8} {:>8} {:>4}",
+      "column", "ones", "ratio", "entropy", "tie"
+    )?;
+
+    for stats in self.columns.iter() {
+      writeln!(
+        f,
+        "{:>6} {:>8} {:>8.3} {:>8.3} {:>4}",
+        stats.column,
+        stats.ones,
+        stats.ratio,
+        stats.entropy,
+        if stats.tie { "yes" } else { "no" }
+      )?;
+    }
+
+    write!(
+      f,
+      "{} row(s), {} column pair(s)",
+      self.rows,
+      self.pairs.len()
+    )?;
+
+    for pair in self.pairs.iter() {
+      write!(
+        f,
+        "\n  bits {} and {}: {}",
+        pair.columns.0, pair.columns.1, pair.relation
+      )?;
+    }
+
+    Ok(())
+  }
+}
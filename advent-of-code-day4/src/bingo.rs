@@ -1,11 +1,14 @@
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::io::{BufRead, BufReader, Read};
 
 use colored::Colorize;
-use eyre::eyre;
 use itertools::Itertools;
 
-use aoc_core::Result;
+use aoc_core::{InputError, ParseError, ValidationError};
+
+use crate::leaderboard::Leaderboard;
+use crate::pattern::WinPattern;
 
 #[derive(Clone)]
 struct Cell {
@@ -23,14 +26,65 @@ impl fmt::Display for Cell {
   }
 }
 
+// Boards get their size from the input, rows being lines of numbers which all have the same width.
 #[derive(Clone)]
-pub struct Board<const N: usize> {
+pub struct Board {
+  width: usize,
   cells: Vec<Cell>,
+  // layout of the board size, with the number of marked cells of each of its lines
+  layout: usize,
+  line_marks: Vec<usize>,
+  completion: Option<Completion>,
+}
+
+struct Line {
+  pattern: usize,
+  len: usize,
+}
+
+// Winning lines of a board size, shared by all the boards of that size, and the lines going
+// through each cell.
+struct Layout {
+  lines: Vec<Line>,
+  cell_lines: Vec<Vec<usize>>,
 }
 
-impl<const N: usize> fmt::Display for Board<N> {
+impl Layout {
+  fn new(width: usize, height: usize, patterns: &[WinPattern]) -> Self {
+    let mut lines = vec![];
+    let mut cell_lines = vec![vec![]; width * height];
+
+    for (pattern, cells) in patterns.iter().enumerate().flat_map(|(i, pattern)| {
+      pattern
+        .get_lines(width, height)
+        .into_iter()
+        .map(move |cells| (i, cells))
+    }) {
+      for cell in cells.iter() {
+        cell_lines[*cell].push(lines.len());
+      }
+
+      lines.push(Line {
+        pattern,
+        len: cells.len(),
+      });
+    }
+
+    Self { lines, cell_lines }
+  }
+}
+
+#[derive(Clone)]
+pub struct Completion {
+  // number of numbers drawn when the board won
+  pub turn: usize,
+  pub drawn_number: usize,
+  pub pattern: WinPattern,
+}
+
+impl fmt::Display for Board {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    for cells in self.cells.chunks(Self::WIDTH) {
+    for cells in self.cells.chunks(self.width) {
       for cell in cells {
         write!(f, "{}", cell)?;
       }
@@ -41,54 +95,46 @@ impl<const N: usize> fmt::Display for Board<N> {
   }
 }
 
-impl<const N: usize> Board<N> {
-  const WIDTH: usize = N;
-  const SIZE: usize = N * N;
-
-  fn from_str(s: &str) -> Result<Self> {
-    let cells = s
-      .split_whitespace()
-      .map(|s| s.parse::<usize>().map_err(Into::into))
-      .map_ok(|value| Cell {
-        marked: false,
-        value,
+impl Board {
+  fn from_lines<'a>(lines: impl Iterator<Item = &'a str>) -> Result<Self, InputError> {
+    let rows = lines
+      .map(|line| {
+        line
+          .split_whitespace()
+          .map(|s| s.parse::<usize>())
+          .map_ok(|value| Cell {
+            marked: false,
+            value,
+          })
+          .collect::<Result<Vec<_>, _>>()
       })
-      .collect::<Result<Vec<_>>>()?;
-
-    if cells.len() != Self::SIZE {
-      return Err(eyre!(
-        "Invalid board size (expected {} but got {})",
-        Self::SIZE,
-        cells.len()
-      ));
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(ParseError::from)?;
+
+    let width = rows
+      .first()
+      .map(Vec::len)
+      .ok_or(ValidationError::Empty { kind: "board" })?;
+
+    if rows.iter().any(|row| row.len() != width) {
+      return Err(ValidationError::UnevenRows { kind: "board" }.into());
     }
 
-    Ok(Self { cells })
+    Ok(Self {
+      width,
+      cells: rows.concat(),
+      layout: 0,
+      line_marks: vec![],
+      completion: None,
+    })
   }
 
-  fn mark_cell(&mut self, value: usize) -> bool {
-    if let Some(cell) = self.cells.iter_mut().find(|cell| cell.value == value) {
-      cell.marked = true;
-
-      true
-    } else {
-      false
-    }
+  fn get_height(&self) -> usize {
+    self.cells.len() / self.width
   }
 
-  fn is_complete(&self) -> bool {
-    self
-      .cells
-      .chunks(Self::WIDTH)
-      .any(|row| row.iter().all(|cell| cell.marked))
-      || (0..Self::WIDTH).into_iter().any(|i| {
-        self
-          .cells
-          .iter()
-          .skip(i)
-          .step_by(Self::WIDTH)
-          .all(|cell| cell.marked)
-      })
+  pub fn get_completion(&self) -> Option<&Completion> {
+    self.completion.as_ref()
   }
 
   pub fn sum_unmarked_cells(&self) -> usize {
@@ -104,7 +150,12 @@ impl<const N: usize> Board<N> {
 pub struct BingoSubsystem {
   cursor: usize,
   drawn_numbers: Vec<usize>,
-  boards: Vec<Board<5>>,
+  boards: Vec<Board>,
+  // a board wins with the first of these patterns it completes
+  patterns: Vec<WinPattern>,
+  layouts: Vec<Layout>,
+  // board and cell indices of each number, so that a draw only visits the cells it marks
+  index: HashMap<usize, Vec<(usize, usize)>>,
 }
 
 impl fmt::Display for BingoSubsystem {
@@ -128,7 +179,7 @@ impl fmt::Display for BingoSubsystem {
 }
 
 impl BingoSubsystem {
-  pub fn from_reader(reader: impl Read) -> Result<Self> {
+  pub fn from_reader(reader: impl Read, patterns: Vec<WinPattern>) -> Result<Self, InputError> {
     let mut reader = BufReader::new(reader);
     let mut buffer = String::new();
 
@@ -136,44 +187,145 @@ impl BingoSubsystem {
 
     let drawn_numbers = buffer
       .split(',')
-      .map(|s| s.trim().parse::<usize>().map_err(Into::into))
-      .collect::<Result<Vec<_>>>()?;
+      .map(|s| s.trim().parse::<usize>())
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(ParseError::from)?;
 
     buffer.clear();
     reader.read_to_string(&mut buffer)?;
 
-    let boards = buffer
-      .split("\n\n")
-      .map(Board::from_str)
-      .collect::<Result<Vec<_>>>()?;
+    // boards are separated by blank lines, whatever the line endings, and start after the
+    // drawn numbers line
+    let mut boards = vec![];
+    let mut first_lines = vec![];
+
+    for (is_blank, lines) in &buffer
+      .lines()
+      .enumerate()
+      .group_by(|(_, line)| line.trim().is_empty())
+    {
+      if !is_blank {
+        let mut lines = lines.peekable();
+
+        first_lines.push(lines.peek().map_or(0, |(i, _)| i + 2));
+        boards.push(Board::from_lines(lines.map(|(_, line)| line))?);
+      }
+    }
+
+    // boards missing the blank line between them look like a board as many times taller
+    let sizes = boards
+      .iter()
+      .map(|board| (board.width, board.get_height()))
+      .collect::<HashSet<_>>();
+
+    for (board, line) in boards.iter().zip(first_lines) {
+      let height = board.get_height();
+      let stacked = sizes
+        .iter()
+        .filter(|(width, other_height)| {
+          *width == board.width && *other_height < height && height % other_height == 0
+        })
+        .max_by_key(|(_, other_height)| *other_height);
+
+      if let Some((width, other_height)) = stacked {
+        return Err(InputError::LineValidation {
+          line,
+          source: ValidationError::Size {
+            kind: "board",
+            expected: width * other_height,
+            actual: board.cells.len(),
+          },
+        });
+      }
+    }
+
+    let mut layouts = vec![];
+    let mut layout_indices = HashMap::new();
+    let mut index = HashMap::<_, Vec<_>>::new();
+
+    for (i, board) in boards.iter_mut().enumerate() {
+      let size = (board.width, board.get_height());
+
+      board.layout = *layout_indices.entry(size).or_insert_with(|| {
+        layouts.push(Layout::new(size.0, size.1, &patterns));
+
+        layouts.len() - 1
+      });
+      board.line_marks = vec![0; layouts[board.layout].lines.len()];
+
+      for (j, cell) in board.cells.iter().enumerate() {
+        let positions = index.entry(cell.value).or_default();
+
+        // only the first cell of a number repeated on a board gets marked
+        if positions.last().map_or(true, |(last, _)| *last != i) {
+          positions.push((i, j));
+        }
+      }
+    }
 
     Ok(Self {
       cursor: 0,
       drawn_numbers,
       boards,
+      patterns,
+      layouts,
+      index,
     })
   }
 
-  pub fn draw_number(&mut self) -> Option<(usize, Option<Board<5>>)> {
+  pub fn get_board(&self, i: usize) -> &Board {
+    &self.boards[i]
+  }
+
+  // Gives the drawn number with the indices of all the boards it completed.
+  pub fn draw_number(&mut self) -> Option<(usize, Vec<usize>)> {
     if self.cursor >= self.drawn_numbers.len() {
       return None;
     }
 
-    let mut winning_board = None;
+    let mut winning_boards = vec![];
     let drawn_number = self.drawn_numbers[self.cursor];
 
     self.cursor += 1;
 
-    for board in self.boards.iter_mut() {
-      if !board.is_complete() {
-        board.mark_cell(drawn_number);
+    for (i, cell) in self.index.get(&drawn_number).into_iter().flatten() {
+      let board = &mut self.boards[*i];
+      let layout = &self.layouts[board.layout];
+
+      if board.completion.is_some() {
+        continue;
+      }
+
+      board.cells[*cell].marked = true;
 
-        if winning_board.is_none() && board.is_complete() {
-          winning_board = Some(board.clone());
+      // lines are listed in pattern order, so the first one completed gives the winning pattern
+      let mut pattern = None;
+
+      for line in layout.cell_lines[*cell].iter() {
+        board.line_marks[*line] += 1;
+
+        if board.line_marks[*line] == layout.lines[*line].len && pattern.is_none() {
+          pattern = Some(layout.lines[*line].pattern);
         }
       }
+
+      if let Some(pattern) = pattern {
+        board.completion = Some(Completion {
+          turn: self.cursor,
+          drawn_number,
+          pattern: self.patterns[pattern].clone(),
+        });
+        winning_boards.push(*i);
+      }
     }
 
-    Some((drawn_number, winning_board))
+    Some((drawn_number, winning_boards))
+  }
+
+  // Draws the remaining numbers and ranks every board.
+  pub fn play(&mut self) -> Leaderboard {
+    while self.draw_number().is_some() {}
+
+    Leaderboard::new(&self.boards)
   }
 }
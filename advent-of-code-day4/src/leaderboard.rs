@@ -0,0 +1,129 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::bingo::Board;
+
+#[derive(Debug, Serialize)]
+pub struct Win {
+  // boards winning on the same turn share the same rank, and the next rank is skipped
+  pub rank: usize,
+  pub tied: bool,
+  pub turn: usize,
+  pub drawn_number: usize,
+  pub pattern: String,
+  pub score: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Standing {
+  // 1-based position of the board in the input
+  pub board: usize,
+  pub unmarked_sum: usize,
+  pub win: Option<Win>,
+}
+
+// Every board ordered by finishing turn then input position, the boards which never won last.
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+pub struct Leaderboard(Vec<Standing>);
+
+impl Leaderboard {
+  pub fn new(boards: &[Board]) -> Self {
+    let mut turns = boards
+      .iter()
+      .filter_map(|board| board.get_completion().map(|completion| completion.turn))
+      .collect::<Vec<_>>();
+
+    turns.sort_unstable();
+
+    let mut standings = boards
+      .iter()
+      .enumerate()
+      .map(|(i, board)| {
+        let unmarked_sum = board.sum_unmarked_cells();
+
+        Standing {
+          board: i + 1,
+          unmarked_sum,
+          win: board.get_completion().map(|completion| {
+            // boards which won earlier, then along with this one
+            let earlier = turns.partition_point(|turn| *turn < completion.turn);
+            let along = turns.partition_point(|turn| *turn <= completion.turn);
+
+            Win {
+              rank: earlier + 1,
+              tied: along - earlier > 1,
+              turn: completion.turn,
+              drawn_number: completion.drawn_number,
+              pattern: completion.pattern.to_string(),
+              score: completion.drawn_number * unmarked_sum,
+            }
+          }),
+        }
+      })
+      .collect::<Vec<_>>();
+
+    standings.sort_by_key(|standing| {
+      (
+        standing.win.as_ref().map_or(usize::MAX, |win| win.rank),
+        standing.board,
+      )
+    });
+
+    Self(standings)
+  }
+
+  pub fn get_first_win(&self) -> Option<&Win> {
+    self.0.first().and_then(|standing| standing.win.as_ref())
+  }
+
+  // The first board, in input order, of those which won on the latest turn.
+  pub fn get_last_win(&self) -> Option<&Win> {
+    let last_rank = self
+      .0
+      .iter()
+      .filter_map(|standing| standing.win.as_ref())
+      .map(|win| win.rank)
+      .max()?;
+
+    self
+      .0
+      .iter()
+      .filter_map(|standing| standing.win.as_ref())
+      .find(|win| win.rank == last_rank)
+  }
+}
+
+impl fmt::Display for Leaderboard {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{:>5} {:>6} {:>5} {:>7} {:>9} {:>7}  pattern",
+      "rank", "board", "turn", "number", "unmarked", "score"
+    )?;
+
+    for standing in self.0.iter() {
+      match &standing.win {
+        Some(win) => write!(
+          f,
+          "\n{:>5} {:>6} {:>5} {:>7} {:>9} {:>7}  {}",
+          format!("{}{}", win.rank, if win.tied { "=" } else { "" }),
+          standing.board,
+          win.turn,
+          win.drawn_number,
+          standing.unmarked_sum,
+          win.score,
+          win.pattern
+        )?,
+        None => write!(
+          f,
+          "\n{:>5} {:>6} {:>5} {:>7} {:>9} {:>7}  -",
+          "-", standing.board, "-", "-", standing.unmarked_sum, "-"
+        )?,
+      }
+    }
+
+    Ok(())
+  }
+}
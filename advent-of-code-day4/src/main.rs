@@ -1,104 +1,261 @@
 mod bingo;
+mod leaderboard;
+mod pattern;
 
-use std::fs::File;
-use std::path::PathBuf;
-
-use aoc_core::{Puzzle, Result};
+use aoc_core::{Puzzle, Result, Runner, RunnerOpt};
 use structopt::StructOpt;
 
 use bingo::BingoSubsystem;
+use pattern::WinPattern;
 
 #[derive(StructOpt)]
 struct Opt {
-  #[structopt(short, long)]
-  filename: PathBuf,
-  #[structopt(short, long)]
-  puzzle: Puzzle,
+  #[structopt(flatten)]
+  runner: RunnerOpt,
+  /// Winning patterns, among row, column, diagonal, anti-diagonal, corners, x, blackout and
+  /// `mask:<rows>` such as `mask:101/010/101`, defaults to rows and columns
+  #[structopt(long, number_of_values = 1)]
+  pattern: Vec<WinPattern>,
+  /// Print every board with its finishing turn and score instead of solving the puzzle
+  #[structopt(long)]
+  leaderboard: bool,
   #[structopt(short, long)]
   debug: bool,
 }
 
 fn solve_puzzle(mut bingo: BingoSubsystem, puzzle: Puzzle, debug: bool) -> usize {
-  let mut last_win = None;
-
-  while let Some((drawn_number, board)) = bingo.draw_number() {
-    if debug {
+  if debug {
+    while let Some((drawn_number, winning_boards)) = bingo.draw_number() {
       println!("Bingo status (drawn number {}):\n{}", drawn_number, bingo);
-    }
 
-    if let Some(board) = board {
-      if debug {
-        println!("Found winning board:\n{}", board);
-      }
+      for i in winning_boards {
+        let board = bingo.get_board(i);
 
-      last_win = Some((drawn_number, board));
-
-      if let Puzzle::Part1 = puzzle {
-        break;
+        if let Some(completion) = board.get_completion() {
+          println!(
+            "Found winning board {} ({}):\n{}",
+            i + 1,
+            completion.pattern,
+            board
+          );
+        }
       }
     }
   }
 
-  last_win
-    .map(|(drawn_number, board)| drawn_number * board.sum_unmarked_cells())
-    .unwrap_or_default()
+  let leaderboard = bingo.play();
+
+  if debug {
+    println!("{}", leaderboard);
+  }
+
+  let win = if let Puzzle::Part1 = puzzle {
+    leaderboard.get_first_win()
+  } else {
+    leaderboard.get_last_win()
+  };
+
+  win.map(|win| win.score).unwrap_or_default()
 }
 
 fn main() -> Result<()> {
   color_eyre::install()?;
 
   let opt = Opt::from_args();
-  let bingo = BingoSubsystem::from_reader(File::open(opt.filename)?)?;
-  let result = solve_puzzle(bingo, opt.puzzle, opt.debug);
+  let debug = opt.debug;
+  let patterns = match opt.pattern {
+    patterns if patterns.is_empty() => WinPattern::DEFAULT.to_vec(),
+    patterns => patterns,
+  };
 
-  println!("{}", result);
+  let runner = Runner::new("day4", opt.runner)?;
 
-  Ok(())
+  if opt.leaderboard {
+    runner.run(move |input, _| Ok(BingoSubsystem::from_reader(&input[..], patterns)?.play()))
+  } else {
+    runner.run(move |input, puzzle| {
+      let bingo = BingoSubsystem::from_reader(&input[..], patterns)?;
+
+      Ok(solve_puzzle(bingo, puzzle, debug))
+    })
+  }
 }
 
 #[cfg(test)]
 mod test {
   use super::*;
 
-  const EXAMPLE_DATA: &str = r"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+  use aoc_core::{InputError, ValidationError};
+
+  use pattern::Mask;
 
-22 13 17 11  0
- 8  2 23  4 24
-21  9 14 16  7
- 6 10  3 18  5
- 1 12 20 15 19
+  aoc_test::example_tests!(|input: &str, puzzle| {
+    let bingo = BingoSubsystem::from_reader(input.as_bytes(), WinPattern::DEFAULT.to_vec())?;
 
- 3 15  0  2 22
- 9 18 13 17  5
-19  8  7 25 23
-20 11 10 24  4
-14 21 16 12  6
+    Ok(solve_puzzle(bingo, puzzle, true))
+  });
 
-14 21 17 24  4
-10 16 15  9 19
-18  8 23 26 20
-22 11 13  6  5
- 2  0 12  3  7";
+  #[test]
+  fn it_renders_bingo_subsystem() -> Result<()> {
+    let mut bingo = BingoSubsystem::from_reader(
+      aoc_test::example!("example").input.as_bytes(),
+      WinPattern::DEFAULT.to_vec(),
+    )?;
+
+    while matches!(bingo.draw_number(), Some((_, winning_boards)) if winning_boards.is_empty()) {}
 
-  const EXAMPLE_RESPONSE_PART1: usize = 4512;
-  const EXAMPLE_RESPONSE_PART2: usize = 1924;
+    insta::assert_snapshot!(aoc_test::render(&bingo));
+
+    Ok(())
+  }
 
   #[test]
-  fn it_solves_example_input_part1() -> Result<()> {
-    let bingo = BingoSubsystem::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(bingo, Puzzle::Part1, true);
+  fn it_plays_boards_of_any_size() -> Result<()> {
+    let input = "1,2,3,4,5,6,7,8,9\n\n1 2 3\n4 5 6\n\n7 8 9\n10 11 12\n13 14 15\n";
+
+    assert_eq!(
+      solve_puzzle(
+        BingoSubsystem::from_reader(input.as_bytes(), WinPattern::DEFAULT.to_vec())?,
+        Puzzle::Part1,
+        false
+      ),
+      3 * (4 + 5 + 6)
+    );
+    assert_eq!(
+      solve_puzzle(
+        BingoSubsystem::from_reader(input.as_bytes(), WinPattern::DEFAULT.to_vec())?,
+        Puzzle::Part2,
+        false
+      ),
+      9 * (10 + 11 + 12 + 13 + 14 + 15)
+    );
 
-    assert_eq!(EXAMPLE_RESPONSE_PART1, result);
+    assert!(matches!(
+      BingoSubsystem::from_reader("1,2\n\n1 2\n3\n".as_bytes(), vec![]),
+      Err(InputError::Validation(ValidationError::UnevenRows { .. }))
+    ));
 
     Ok(())
   }
 
   #[test]
-  fn it_solves_example_input_part2() -> Result<()> {
-    let bingo = BingoSubsystem::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(bingo, Puzzle::Part2, true);
+  fn it_splits_boards_on_blank_lines() -> Result<()> {
+    let input = aoc_test::example!("example").input;
+    let crlf_input = input.replace('\n', "\r\n");
+
+    assert_eq!(
+      solve_puzzle(
+        BingoSubsystem::from_reader(crlf_input.as_bytes(), WinPattern::DEFAULT.to_vec())?,
+        Puzzle::Part1,
+        false
+      ),
+      4512
+    );
+
+    // the last two boards run into each other without the blank line
+    let stacked_input = input.replacen("\n\n14 21 17", "\n14 21 17", 1);
+    let error =
+      BingoSubsystem::from_reader(stacked_input.as_bytes(), WinPattern::DEFAULT.to_vec()).err();
+
+    assert!(matches!(
+      error,
+      Some(InputError::LineValidation {
+        line: 9,
+        source: ValidationError::Size {
+          expected: 25,
+          actual: 50,
+          ..
+        }
+      })
+    ));
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_reports_winning_patterns() -> Result<()> {
+    let input = "2,1,5,9,3,7,4\n\n1 2 3\n4 5 6\n7 8 9\n";
+    let find_win = |patterns: &str| -> Result<Option<(usize, String)>> {
+      let patterns = patterns
+        .split(' ')
+        .map(str::parse)
+        .collect::<std::result::Result<Vec<WinPattern>, _>>()?;
+      let leaderboard = BingoSubsystem::from_reader(input.as_bytes(), patterns)?.play();
+
+      Ok(
+        leaderboard
+          .get_first_win()
+          .map(|win| (win.drawn_number, win.pattern.clone())),
+      )
+    };
+
+    assert_eq!(find_win("row column")?, Some((3, "row".to_string())));
+    assert_eq!(find_win("column x")?, Some((7, "x".to_string())));
+    assert_eq!(
+      find_win("anti-diagonal")?,
+      Some((7, "anti-diagonal".to_string()))
+    );
+    assert_eq!(
+      find_win("corners blackout")?,
+      Some((7, "corners".to_string()))
+    );
+    assert_eq!(find_win("blackout")?, None);
+    assert_eq!(
+      find_win("mask:010/111 mask:11/11")?,
+      Some((4, "mask:11/11".to_string()))
+    );
+    // a mask without any cell to mark would complete a board on its first mark
+    assert!("mask:00/00".parse::<WinPattern>().is_err());
+    assert_eq!(
+      "101/0x1".parse::<Mask>().err().map(|e| e.to_string()),
+      Some("invalid 'x' mask cell at column 6".to_string())
+    );
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_ranks_every_board() -> Result<()> {
+    let input = "1,4,2,5,3\n\n1 2\n7 8\n\n4 5\n8 9\n\n1 4\n6 7\n\n2 7\n8 9\n\n6 2\n6 1\n";
+    let create_bingo =
+      || BingoSubsystem::from_reader(input.as_bytes(), WinPattern::DEFAULT.to_vec());
+
+    // the first and fifth boards both win on the third turn
+    insta::assert_snapshot!(create_bingo()?.play().to_string());
+
+    assert_eq!(solve_puzzle(create_bingo()?, Puzzle::Part1, false), 4 * 13);
+    assert_eq!(solve_puzzle(create_bingo()?, Puzzle::Part2, false), 5 * 17);
+
+    Ok(())
+  }
+
+  #[test]
+  fn it_marks_numbers_through_the_index() -> Result<()> {
+    let board = "1 2 3\n4 5 6\n7 8 9\n\n";
+    let input = format!("7,9,4,2,6,1,5\n\n{}9 9 9\n3 3 3\n", board.repeat(1000));
+    let leaderboard =
+      BingoSubsystem::from_reader(input.as_bytes(), WinPattern::DEFAULT.to_vec())?.play();
+
+    // the repeated 9 only marks one cell of the last board, which never wins
+    let win = leaderboard
+      .get_first_win()
+      .map(|win| (win.rank, win.tied, win.turn));
 
-    assert_eq!(EXAMPLE_RESPONSE_PART2, result);
+    assert_eq!(win, Some((1, true, 6)));
+    assert_eq!(
+      leaderboard
+        .get_last_win()
+        .map(|win| (win.rank, win.tied, win.turn)),
+      win
+    );
+    assert_eq!(
+      leaderboard
+        .to_string()
+        .lines()
+        .last()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>()),
+      Some(vec!["-", "1001", "-", "-", "27", "-", "-"])
+    );
 
     Ok(())
   }
@@ -0,0 +1,147 @@
+use std::fmt;
+use std::str::FromStr;
+
+use parse_display::{Display, FromStr};
+
+use aoc_core::ParseError;
+
+// Grid of the cells to mark, rows being separated by slashes, e.g. `11/11` for any 2x2 square.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mask {
+  width: usize,
+  cells: Vec<bool>,
+}
+
+impl FromStr for Mask {
+  type Err = ParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let rows = s.split('/').collect::<Vec<_>>();
+    let width = rows[0].len();
+
+    if width == 0 || rows.iter().any(|row| row.len() != width) {
+      return Err(ParseError::Malformed {
+        kind: "mask",
+        value: s.to_string(),
+      });
+    }
+
+    // rows all have the same width, so every row starts right after the previous one and its `/`
+    let cells = rows
+      .iter()
+      .enumerate()
+      .flat_map(|(y, row)| {
+        row
+          .bytes()
+          .enumerate()
+          .map(move |(x, byte)| (y * (width + 1) + x, byte))
+      })
+      .map(|(i, byte)| match byte {
+        b'1' => Ok(true),
+        b'0' => Ok(false),
+        _ => Err(ParseError::InvalidCharAt {
+          kind: "mask cell",
+          value: byte as char,
+          column: i + 1,
+        }),
+      })
+      .collect::<Result<Vec<_>, _>>()?;
+
+    // a mask without any cell to mark would win before the first draw
+    if !cells.contains(&true) {
+      return Err(ParseError::Malformed {
+        kind: "mask",
+        value: s.to_string(),
+      });
+    }
+
+    Ok(Self { width, cells })
+  }
+}
+
+impl fmt::Display for Mask {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, cells) in self.cells.chunks(self.width).enumerate() {
+      if i > 0 {
+        write!(f, "/")?;
+      }
+
+      for cell in cells {
+        write!(f, "{}", *cell as usize)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Display, FromStr)]
+#[display(style = "kebab-case")]
+pub enum WinPattern {
+  Row,
+  Column,
+  Diagonal,
+  AntiDiagonal,
+  Corners,
+  X,
+  Blackout,
+  #[display("mask:{0}")]
+  Mask(Mask),
+}
+
+impl WinPattern {
+  pub const DEFAULT: [Self; 2] = [Self::Row, Self::Column];
+
+  // Lists the sets of cells winning once all of them are marked, as indices in a board of the given
+  // size. Diagonals only exist on square boards, and masks are tried at every offset they fit in.
+  pub fn get_lines(&self, width: usize, height: usize) -> Vec<Vec<usize>> {
+    let index = |x: usize, y: usize| y * width + x;
+    let diagonal = (0..width).map(|i| index(i, i)).collect::<Vec<_>>();
+    let anti_diagonal = (0..width)
+      .map(|i| index(width - 1 - i, i))
+      .collect::<Vec<_>>();
+
+    match self {
+      Self::Row => (0..height)
+        .map(|y| (0..width).map(|x| index(x, y)).collect())
+        .collect(),
+      Self::Column => (0..width)
+        .map(|x| (0..height).map(|y| index(x, y)).collect())
+        .collect(),
+      Self::Diagonal | Self::AntiDiagonal | Self::X if width != height => vec![],
+      Self::Diagonal => vec![diagonal],
+      Self::AntiDiagonal => vec![anti_diagonal],
+      Self::X => {
+        let mut cells = diagonal;
+
+        cells.extend(anti_diagonal);
+        cells.sort_unstable();
+        cells.dedup();
+
+        vec![cells]
+      }
+      Self::Corners => vec![vec![
+        index(0, 0),
+        index(width - 1, 0),
+        index(0, height - 1),
+        index(width - 1, height - 1),
+      ]],
+      Self::Blackout => vec![(0..width * height).collect()],
+      Self::Mask(mask) => {
+        let mask_height = mask.cells.len() / mask.width;
+        let offsets_x = 0..(width + 1).saturating_sub(mask.width);
+        let offsets_y = 0..(height + 1).saturating_sub(mask_height);
+
+        offsets_y
+          .flat_map(|y| offsets_x.clone().map(move |x| (x, y)))
+          .map(|(x, y)| {
+            (0..mask.cells.len())
+              .filter(|i| mask.cells[*i])
+              .map(|i| index(x + i % mask.width, y + i / mask.width))
+              .collect()
+          })
+          .collect()
+      }
+    }
+  }
+}
@@ -1,6 +1,6 @@
 use std::io::{BufRead, BufReader, Read};
 
-use aoc_core::Result;
+use aoc_core::{InputError, ParseError};
 
 const BIRTH_CYCLE: usize = 6;
 const FIRST_BIRTH_CYCLE: usize = BIRTH_CYCLE + 2;
@@ -25,7 +25,7 @@ impl Lanternfish {
 pub struct LanternfishSimulator(Vec<(Lanternfish, usize)>);
 
 impl LanternfishSimulator {
-  pub fn from_reader(reader: impl Read) -> Result<Self> {
+  pub fn from_reader(reader: impl Read) -> Result<Self, InputError> {
     let mut reader = BufReader::new(reader);
     let mut buffer = String::new();
 
@@ -33,13 +33,9 @@ impl LanternfishSimulator {
 
     let lanternfishs = buffer
       .split(',')
-      .map(|s| {
-        s.trim()
-          .parse::<usize>()
-          .map(|x| (Lanternfish(x), 1))
-          .map_err(Into::into)
-      })
-      .collect::<Result<Vec<_>>>()?;
+      .map(|s| s.trim().parse::<usize>().map(|x| (Lanternfish(x), 1)))
+      .collect::<Result<Vec<_>, _>>()
+      .map_err(ParseError::from)?;
 
     let mut simulator = LanternfishSimulator(Vec::with_capacity(FIRST_BIRTH_CYCLE));
 
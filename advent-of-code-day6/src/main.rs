@@ -1,26 +1,21 @@
 mod lanternfish;
 
-use std::fs::File;
-use std::path::PathBuf;
-
-use aoc_core::{Puzzle, Result};
+use aoc_core::{Puzzle, Result, Runner, RunnerOpt};
 use structopt::StructOpt;
 
 use lanternfish::LanternfishSimulator;
 
 #[derive(StructOpt)]
 struct Opt {
-  #[structopt(short, long)]
-  filename: PathBuf,
-  #[structopt(short, long)]
-  puzzle: Puzzle,
+  #[structopt(flatten)]
+  runner: RunnerOpt,
 }
 
-fn solve_puzzle(mut simulator: LanternfishSimulator, puzzle: Puzzle) -> usize {
-  let days = match puzzle {
+fn solve_puzzle(mut simulator: LanternfishSimulator, puzzle: Puzzle, days: Option<usize>) -> usize {
+  let days = days.unwrap_or(match puzzle {
     Puzzle::Part1 => 80,
     Puzzle::Part2 => 256,
-  };
+  });
 
   simulator.simulate(days);
 
@@ -31,40 +26,23 @@ fn main() -> Result<()> {
   color_eyre::install()?;
 
   let opt = Opt::from_args();
-  let simulator = LanternfishSimulator::from_reader(File::open(opt.filename)?)?;
-  let result = solve_puzzle(simulator, opt.puzzle);
+  let runner = Runner::new("day6", opt.runner)?;
+  let days = runner.get_parameter("days");
 
-  println!("{}", result);
+  runner.run(move |input, puzzle| {
+    let simulator = LanternfishSimulator::from_reader(&input[..])?;
 
-  Ok(())
+    Ok(solve_puzzle(simulator, puzzle, days))
+  })
 }
 
 #[cfg(test)]
 mod test {
   use super::*;
 
-  const EXAMPLE_DATA: &str = r"3,4,3,1,2";
-
-  const EXAMPLE_RESPONSE_PART1: usize = 5934;
-  const EXAMPLE_RESPONSE_PART2: usize = 26984457539;
-
-  #[test]
-  fn it_solves_example_input_part1() -> Result<()> {
-    let simulator = LanternfishSimulator::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(simulator, Puzzle::Part1);
-
-    assert_eq!(EXAMPLE_RESPONSE_PART1, result);
-
-    Ok(())
-  }
-
-  #[test]
-  fn it_solves_example_input_part2() -> Result<()> {
-    let simulator = LanternfishSimulator::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(simulator, Puzzle::Part2);
-
-    assert_eq!(EXAMPLE_RESPONSE_PART2, result);
+  aoc_test::example_tests!(|input: &str, puzzle| {
+    let simulator = LanternfishSimulator::from_reader(input.as_bytes())?;
 
-    Ok(())
-  }
+    Ok(solve_puzzle(simulator, puzzle, None))
+  });
 }
@@ -1,15 +1,10 @@
-use std::fs::File;
-use std::path::PathBuf;
-
-use aoc_core::{Puzzle, Result};
+use aoc_core::{Puzzle, Result, Runner, RunnerOpt};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
 struct Opt {
-  #[structopt(short, long)]
-  filename: PathBuf,
-  #[structopt(short, long)]
-  puzzle: Puzzle,
+  #[structopt(flatten)]
+  runner: RunnerOpt,
 }
 
 fn solve_puzzle(mut positions: Vec<isize>, puzzle: Puzzle) -> isize {
@@ -42,40 +37,21 @@ fn main() -> Result<()> {
   color_eyre::install()?;
 
   let opt = Opt::from_args();
-  let crabs = aoc_core::split_line(File::open(opt.filename)?, ",")?;
-  let result = solve_puzzle(crabs, opt.puzzle);
 
-  println!("{}", result);
+  Runner::new("day7", opt.runner)?.run(move |input, puzzle| {
+    let crabs = aoc_core::split_line(&input[..], ",")?;
 
-  Ok(())
+    Ok(solve_puzzle(crabs, puzzle))
+  })
 }
 
 #[cfg(test)]
 mod test {
   use super::*;
 
-  const EXAMPLE_DATA: &str = r"16,1,2,0,4,2,7,1,2,14";
-
-  const EXAMPLE_RESPONSE_PART1: isize = 37;
-  const EXAMPLE_RESPONSE_PART2: isize = 168;
-
-  #[test]
-  fn it_solves_example_input_part1() -> Result<()> {
-    let positions = aoc_core::split_line(EXAMPLE_DATA.as_bytes(), ",")?;
-    let result = solve_puzzle(positions, Puzzle::Part1);
+  aoc_test::example_tests!(|input: &str, puzzle| {
+    let positions = aoc_core::split_line(input.as_bytes(), ",")?;
 
-    assert_eq!(EXAMPLE_RESPONSE_PART1, result);
-
-    Ok(())
-  }
-
-  #[test]
-  fn it_solves_example_input_part2() -> Result<()> {
-    let positions = aoc_core::split_line(EXAMPLE_DATA.as_bytes(), ",")?;
-    let result = solve_puzzle(positions, Puzzle::Part2);
-
-    assert_eq!(EXAMPLE_RESPONSE_PART2, result);
-
-    Ok(())
-  }
+    Ok(solve_puzzle(positions, puzzle))
+  });
 }
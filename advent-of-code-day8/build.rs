@@ -0,0 +1,3 @@
+fn main() {
+  aoc_test::generate_example_tests();
+}
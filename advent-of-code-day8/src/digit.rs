@@ -1,9 +1,8 @@
-use eyre::eyre;
 use itertools::Itertools;
 use std::io::Read;
 use std::str::FromStr;
 
-use aoc_core::{Error, Result};
+use aoc_core::{InputError, ParseError};
 
 struct SignalReport {
   unique_digits: Vec<String>,
@@ -17,15 +16,18 @@ fn split_signals(s: &str) -> Vec<String> {
 }
 
 impl FromStr for SignalReport {
-  type Err = Error;
+  type Err = ParseError;
 
-  fn from_str(s: &str) -> Result<Self> {
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
     let (unique_digits, output_digits) = s
       .split_once('|')
       .map(|(unique_digits, output_digits)| {
         (split_signals(unique_digits), split_signals(output_digits))
       })
-      .ok_or_else(|| eyre!("Missing '|' separator"))?;
+      .ok_or(ParseError::MissingSeparator {
+        kind: "signal report",
+        separator: "|",
+      })?;
 
     Ok(Self {
       unique_digits,
@@ -37,7 +39,7 @@ impl FromStr for SignalReport {
 pub struct DisplayInterpreter(Vec<SignalReport>);
 
 impl DisplayInterpreter {
-  pub fn from_reader(reader: impl Read) -> Result<Self> {
+  pub fn from_reader(reader: impl Read) -> Result<Self, InputError> {
     let signal_reports = aoc_core::read_lines(reader)?;
 
     Ok(Self(signal_reports))
@@ -141,6 +143,8 @@ fn create_masks(digits: &str) -> Vec<(usize, [u8; 7])> {
 mod tests {
   use super::*;
 
+  use aoc_core::Result;
+
   #[test]
   fn it_verifies_digits() -> Result<()> {
     let input =
@@ -1,19 +1,15 @@
 mod heightmap;
 
-use std::fs::File;
-use std::path::PathBuf;
 use structopt::StructOpt;
 
-use aoc_core::{Puzzle, Result};
+use aoc_core::{Puzzle, Result, Runner, RunnerOpt};
 
 use heightmap::{Cell, HeightMap};
 
 #[derive(StructOpt)]
 struct Opt {
-  #[structopt(short, long)]
-  filename: PathBuf,
-  #[structopt(short, long)]
-  puzzle: Puzzle,
+  #[structopt(flatten)]
+  runner: RunnerOpt,
   #[structopt(short, long)]
   debug: bool,
 }
@@ -47,44 +43,32 @@ fn main() -> Result<()> {
   color_eyre::install()?;
 
   let opt = Opt::from_args();
-  let heightmap = HeightMap::from_reader(File::open(opt.filename)?)?;
-  let result = solve_puzzle(heightmap, opt.puzzle, opt.debug);
+  let debug = opt.debug;
 
-  println!("{}", result);
+  Runner::new("day9", opt.runner)?.run(move |input, puzzle| {
+    let heightmap = HeightMap::from_input(&input)?;
 
-  Ok(())
+    Ok(solve_puzzle(heightmap, puzzle, debug))
+  })
 }
 
 #[cfg(test)]
 mod test {
   use super::*;
 
-  const EXAMPLE_DATA: &str = r"2199943210
-3987894921
-9856789892
-8767896789
-9899965678
-";
+  use aoc_core::Input;
 
-  const EXAMPLE_RESPONSE_PART1: usize = 15;
-  const EXAMPLE_RESPONSE_PART2: usize = 1134;
-
-  #[test]
-  fn it_solves_example_input_part1() -> Result<()> {
-    let heightmap = HeightMap::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(heightmap, Puzzle::Part1, true);
+  aoc_test::example_tests!(|input: &str, puzzle| {
+    let heightmap = HeightMap::from_input(&Input::from(input))?;
 
-    assert_eq!(EXAMPLE_RESPONSE_PART1, result);
-
-    Ok(())
-  }
+    Ok(solve_puzzle(heightmap, puzzle, true))
+  });
 
   #[test]
-  fn it_solves_example_input_part2() -> Result<()> {
-    let heightmap = HeightMap::from_reader(EXAMPLE_DATA.as_bytes())?;
-    let result = solve_puzzle(heightmap, Puzzle::Part2, true);
+  fn it_renders_heightmap() -> Result<()> {
+    let heightmap = HeightMap::from_input(&Input::from(aoc_test::example!("example").input))?;
 
-    assert_eq!(EXAMPLE_RESPONSE_PART2, result);
+    insta::assert_snapshot!(aoc_test::render(&heightmap));
 
     Ok(())
   }
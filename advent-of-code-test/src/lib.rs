@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::env;
+use std::fmt::{self, Debug};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use aoc_core::{InputError, ParseError, Puzzle};
+
+const HEADER_SEPARATOR: &str = "---";
+const EXAMPLE_TESTS: &str = "example_tests.rs";
+
+// An example file starts with a header giving the expected answer of each part, e.g.
+//
+//   part1: 7
+//   part2: 5
+//   ---
+//   <puzzle input>
+//
+// Parts without an expected answer are skipped for that example.
+pub struct Example {
+  pub path: PathBuf,
+  pub input: String,
+  answers: HashMap<String, String>,
+}
+
+impl Example {
+  pub fn from_file(path: impl AsRef<Path>) -> Result<Self, InputError> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path)?;
+
+    let (header, input) = content
+      .split_once(&format!("{}\n", HEADER_SEPARATOR))
+      .ok_or(ParseError::MissingSeparator {
+        kind: "example",
+        separator: HEADER_SEPARATOR,
+      })?;
+
+    let answers = header
+      .lines()
+      .map(str::trim)
+      .enumerate()
+      .filter(|(_, line)| !line.is_empty())
+      .map(|(i, line)| {
+        line
+          .split_once(':')
+          .filter(|(part, _)| part.trim().parse::<Puzzle>().is_ok())
+          .map(|(part, answer)| (part.trim().to_string(), answer.trim().to_string()))
+          .ok_or_else(|| InputError::Line {
+            line: i + 1,
+            source: ParseError::Malformed {
+              kind: "example header",
+              value: line.to_string(),
+            },
+          })
+      })
+      .collect::<Result<HashMap<_, _>, _>>()?;
+
+    Ok(Self {
+      path: path.to_path_buf(),
+      input: input.to_string(),
+      answers,
+    })
+  }
+
+  pub fn get_answer(&self, puzzle: Puzzle) -> Option<&str> {
+    self.answers.get(&puzzle.to_string()).map(String::as_str)
+  }
+}
+
+// Forces colours on so renderings do not depend on the terminal, then turns every
+// `ESC[<params>m` sequence into a readable `{<params>}` marker.
+pub fn render(value: &impl fmt::Display) -> String {
+  colored::control::set_override(true);
+
+  let rendering = value.to_string();
+  let mut chars = rendering.chars().peekable();
+  let mut output = String::with_capacity(rendering.len());
+
+  while let Some(c) = chars.next() {
+    if c == '\x1b' && chars.peek() == Some(&'[') {
+      chars.next();
+      output.push('{');
+      output.extend(chars.by_ref().take_while(|c| *c != 'm'));
+      output.push('}');
+    } else {
+      output.push(c);
+    }
+  }
+
+  output
+}
+
+pub fn run_example<T, F>(path: impl AsRef<Path>, part: &str, solve: F)
+where
+  T: FromStr + PartialEq + Debug,
+  T::Err: Debug,
+  F: Fn(&str, Puzzle) -> aoc_core::Result<T>,
+{
+  let path = path.as_ref();
+  let example =
+    Example::from_file(path).unwrap_or_else(|e| panic!("unable to load {:?}: {:?}", path, e));
+  let puzzle = part
+    .parse::<Puzzle>()
+    .unwrap_or_else(|e| panic!("invalid part {:?}: {:?}", part, e));
+
+  let expected_answer = example
+    .get_answer(puzzle)
+    .unwrap_or_else(|| panic!("{:?}: no {} answer", path, puzzle))
+    .parse::<T>()
+    .unwrap_or_else(|e| panic!("{:?}: invalid {} answer: {:?}", path, puzzle, e));
+
+  match solve(&example.input, puzzle) {
+    Ok(answer) => assert_eq!(answer, expected_answer, "{:?} ({})", path, puzzle),
+    Err(e) => panic!("{:?} ({}): {:?}", path, puzzle, e),
+  }
+}
+
+// Lists the parts of each `examples/*.txt` file with an expected answer, for the
+// `example_tests!(solve)` form to generate their tests. Meant to be called from the build script
+// of each day, so that dropping a new example file is enough to get it tested.
+pub fn generate_example_tests() {
+  let manifest_directory = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set");
+  let directory = Path::new(&manifest_directory).join("examples");
+
+  println!("cargo:rerun-if-changed={}", directory.display());
+
+  let mut paths = match fs::read_dir(&directory) {
+    Ok(entries) => entries
+      .map(|entry| entry.map(|entry| entry.path()))
+      .collect::<Result<Vec<_>, _>>()
+      .unwrap_or_else(|e| panic!("unable to list {:?}: {}", directory, e)),
+    Err(_) => vec![],
+  };
+
+  paths.retain(|path| {
+    path
+      .extension()
+      .map_or(false, |extension| extension == "txt")
+  });
+  paths.sort();
+
+  let mut cases = vec![];
+
+  for path in paths.iter() {
+    println!("cargo:rerun-if-changed={}", path.display());
+
+    let name = path
+      .file_stem()
+      .and_then(|stem| stem.to_str())
+      .filter(|stem| is_identifier(stem))
+      .unwrap_or_else(|| panic!("{:?}: example names must be valid identifiers", path));
+    let example =
+      Example::from_file(path).unwrap_or_else(|e| panic!("unable to load {:?}: {:?}", path, e));
+    let parts = [Puzzle::Part1, Puzzle::Part2]
+      .iter()
+      .filter(|puzzle| example.get_answer(**puzzle).is_some())
+      .map(Puzzle::to_string)
+      .collect::<Vec<_>>();
+
+    if !parts.is_empty() {
+      cases.push(format!("{}: [{}]", name, parts.join(", ")));
+    }
+  }
+
+  let body = if cases.is_empty() {
+    String::new()
+  } else {
+    format!("aoc_test::example_tests!($solve, {});", cases.join(", "))
+  };
+  let output = Path::new(&env::var("OUT_DIR").expect("OUT_DIR is not set")).join(EXAMPLE_TESTS);
+
+  fs::write(
+    &output,
+    format!(
+      "macro_rules! generated_example_tests {{\n  ($solve:expr) => {{\n    {}\n  }};\n}}\n",
+      body
+    ),
+  )
+  .unwrap_or_else(|e| panic!("unable to write {:?}: {}", output, e));
+}
+
+fn is_identifier(name: &str) -> bool {
+  let mut chars = name.chars();
+
+  chars
+    .next()
+    .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// Generates a module per example file, holding one test per part, e.g.
+//
+//   example_tests!(solve, example: [part1, part2], script: [part1]);
+//
+// runs `examples/example.txt` as `example::part1` and `example::part2`, then `examples/script.txt`
+// as `script::part1`. Without any list, the files and parts found by `generate_example_tests` in
+// the build script are used.
+#[macro_export]
+macro_rules! example_tests {
+  ($solve:expr, $($name:ident: [$($part:ident),+ $(,)?]),+ $(,)?) => {
+    $(
+      mod $name {
+        use super::*;
+
+        $(
+          #[test]
+          fn $part() {
+            $crate::run_example(
+              concat!(env!("CARGO_MANIFEST_DIR"), "/examples/", stringify!($name), ".txt"),
+              stringify!($part),
+              $solve,
+            );
+          }
+        )+
+      }
+    )+
+  };
+  ($solve:expr $(,)?) => {
+    include!(concat!(env!("OUT_DIR"), "/example_tests.rs"));
+
+    generated_example_tests!($solve);
+  };
+}
+#[macro_export]
+macro_rules! example {
+  ($name:literal) => {
+    $crate::Example::from_file(concat!(
+      env!("CARGO_MANIFEST_DIR"),
+      "/examples/",
+      $name,
+      ".txt"
+    ))
+    .unwrap_or_else(|e| panic!("unable to load {} example: {:?}", $name, e))
+  };
+}